@@ -1,9 +1,11 @@
-use std::collections::BTreeMap;
+use std::{borrow::Cow, collections::BTreeMap};
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
-    AgentCardSignature, AgentInterface, AgentSkill, JsonStr, SecurityScheme, TransportProtocol,
+    impl_into_owned, negotiate_output_modes, AgentCardSignature, AgentInterface, AgentSkill,
+    JsonObject, JsonRpcError, MediaType, MissingFieldError, SecurityRequirement, SecurityScheme,
+    TransportProtocol,
 };
 
 /// Agent Cards themselves might contain information that is considered sensitive.
@@ -20,16 +22,20 @@ use crate::{
 #[serde(rename_all = "camelCase")]
 pub struct AgentCard<'a> {
     /// The version of the A2A protocol this agent supports. defaults to "0.3.0"
-    protocol_version: &'a str,
+    #[serde(borrow)]
+    protocol_version: Cow<'a, str>,
     ///  A human-readable name for the agent (UTF-8). Example ["Recipe Agent"]
-    name: &'a str,
+    #[serde(borrow)]
+    name: Cow<'a, str>,
     /// A human-readable description of the agent, assisting users and other agents
     /// in understanding its purpose. Examples ["Agent that helps users with recipes and cooking."]
-    description: &'a str,
+    #[serde(borrow)]
+    description: Cow<'a, str>,
     /// The preferred endpoint URL for interacting with the agent.
     /// This URL MUST support the transport specified by 'preferredTransport'.
     /// examples ["https://api.example.com/a2a/v1"]
-    url: &'a str,
+    #[serde(borrow)]
+    url: Cow<'a, str>,
     /// The transport protocol for the preferred endpoint (the main 'url' field).
     /// If not specified, defaults to 'JSONRPC'.
     ///
@@ -39,6 +45,7 @@ pub struct AgentCard<'a> {
     /// default "JSONRPC", examples ["JSONRPC", "GRPC", "HTTP+JSON"]   
     #[serde(serialize_with = "serialize_transport_optional")]
     #[serde(deserialize_with = "deserialize_transport_optional")]
+    #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     preferred_transport: Option<TransportProtocol>,
     /// A list of additional supported interfaces (transport and URL combinations).
@@ -56,21 +63,25 @@ pub struct AgentCard<'a> {
     additional_interfaces: Option<Vec<AgentInterface<'a>>>,
     /// An optional URL to an icon for the agent.
     #[serde(skip_serializing_if = "Option::is_none")]
-    icon_url: Option<&'a str>,
+    #[serde(borrow)]
+    icon_url: Option<Cow<'a, str>>,
     /// Information about the agent's service provider.
     #[serde(skip_serializing_if = "Option::is_none")]
     provider: Option<AgentProvider<'a>>,
     /// The agent's own version number. The format is defined by the provider. examples ["1.0.0"]
-    version: &'a str,
+    #[serde(borrow)]
+    version: Cow<'a, str>,
     /// An optional URL to the agent's documentation.
     #[serde(skip_serializing_if = "Option::is_none")]
-    documentation_url: Option<&'a str>,
+    #[serde(borrow)]
+    documentation_url: Option<Cow<'a, str>>,
     /// A declaration of optional capabilities supported by the agent.
     capabilities: AgentCapabilities<'a>,
     /// A declaration of the security schemes available to authorize requests.
     /// The key is the scheme name. Follows the OpenAPI 3.0 Security Scheme Object.
     #[serde(skip_serializing_if = "Option::is_none")]
-    security_schemes: Option<BTreeMap<&'a str, SecurityScheme<'a>>>,
+    #[serde(borrow)]
+    security_schemes: Option<BTreeMap<Cow<'a, str>, SecurityScheme<'a>>>,
     /// A list of security requirement objects that apply to all agent interactions. Each object
     /// lists security schemes that can be used. Follows the OpenAPI 3.0 Security Requirement Object.
     /// This list can be seen as an OR of ANDs. Each object in the list describes one possible
@@ -78,12 +89,15 @@ pub struct AgentCard<'a> {
     /// for example, "callers must either use OAuth OR an API Key AND mTLS."
     /// examples [[{"oauth": ["read"]}, {"api-key": [], "mtls": []}]]
     #[serde(skip_serializing_if = "Option::is_none")]
-    security: Option<Vec<BTreeMap<&'a str, Vec<&'a str>>>>,
+    #[serde(borrow)]
+    security: Option<Vec<SecurityRequirement<'a>>>,
     ///  Default set of supported input MIME types for all skills, which can be
     ///  overridden on a per-skill basis.
-    default_input_modes: Vec<&'a str>,
+    #[serde(borrow)]
+    default_input_modes: Vec<MediaType<'a>>,
     /// Default set of supported output MIME types for all skills, which can be overridden on a per-skill basis.
-    default_output_modes: Vec<&'a str>,
+    #[serde(borrow)]
+    default_output_modes: Vec<MediaType<'a>>,
     /** The set of skills, or distinct capabilities, that the agent can perform. */
     skills: Vec<AgentSkill<'a>>,
     /// If true, the agent can provide an extended agent card with additional details to authenticated users. Defaults to false.
@@ -95,24 +109,33 @@ pub struct AgentCard<'a> {
 }
 
 impl<'a> AgentCard<'a> {
+    /// The version of the A2A protocol that [AgentCardBuilder] fills in by default.
+    pub const PROTOCOL_VERSION: &'static str = "0.3.0";
+
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Start building a card. `name`, `description`, `url`, `version`, at least one skill
+    /// and at least one default input and output mode are required.
+    pub fn builder() -> AgentCardBuilder<'a> {
+        AgentCardBuilder::new()
+    }
+
     pub fn protocol_version(&self) -> &str {
-        self.protocol_version
+        &self.protocol_version
     }
 
     pub fn name(&self) -> &str {
-        self.name
+        &self.name
     }
 
     pub fn description(&self) -> &str {
-        self.description
+        &self.description
     }
 
     pub fn url(&self) -> &str {
-        self.url
+        &self.url
     }
 
     pub fn preferred_transport(&self) -> Option<&TransportProtocol> {
@@ -128,33 +151,57 @@ impl<'a> AgentCard<'a> {
     }
 
     pub fn icon_url(&self) -> Option<&str> {
-        self.icon_url
+        self.icon_url.as_deref()
     }
 
     pub fn provider(&self) -> Option<&AgentProvider<'_>> {
         self.provider.as_ref()
     }
 
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
     pub fn documentation_url(&self) -> Option<&str> {
-        self.documentation_url
+        self.documentation_url.as_deref()
     }
 
-    pub fn security_schemes(&self) -> Option<&BTreeMap<&'a str, SecurityScheme<'a>>> {
+    pub fn security_schemes(&self) -> Option<&BTreeMap<Cow<'a, str>, SecurityScheme<'a>>> {
         self.security_schemes.as_ref()
     }
 
-    pub fn security(&self) -> Option<&Vec<BTreeMap<&'a str, Vec<&'a str>>>> {
+    pub fn security(&self) -> Option<&Vec<SecurityRequirement<'a>>> {
         self.security.as_ref()
     }
 
-    pub fn default_input_modes(&self) -> &[&str] {
+    pub fn default_input_modes(&self) -> &[MediaType<'a>] {
         self.default_input_modes.as_slice()
     }
 
-    pub fn default_output_modes(&self) -> &[&str] {
+    pub fn default_output_modes(&self) -> &[MediaType<'a>] {
         self.default_output_modes.as_slice()
     }
 
+    pub fn skills(&self) -> &[AgentSkill<'a>] {
+        self.skills.as_slice()
+    }
+
+    /// The output modes of `skill`, or the defaults of the agent when the skill does not override them.
+    pub fn output_modes_of<'s>(&'s self, skill: Option<&'s AgentSkill<'a>>) -> &'s [MediaType<'a>] {
+        skill
+            .and_then(AgentSkill::output_modes)
+            .unwrap_or(self.default_output_modes())
+    }
+
+    /// Intersect the modes the client accepts with the output modes of `skill`, see [negotiate_output_modes].
+    pub fn negotiate_output_modes(
+        &self,
+        skill: Option<&AgentSkill<'a>>,
+        accepted: &[MediaType<'a>],
+    ) -> Result<Vec<MediaType<'a>>, JsonRpcError<'a>> {
+        negotiate_output_modes(accepted, self.output_modes_of(skill))
+    }
+
     pub fn supports_authenticated_extended_card(&self) -> Option<bool> {
         self.supports_authenticated_extended_card
     }
@@ -162,6 +209,187 @@ impl<'a> AgentCard<'a> {
     pub fn signatures(&self) -> Option<&Vec<AgentCardSignature<'a>>> {
         self.signatures.as_ref()
     }
+
+    #[cfg(feature = "jws")]
+    pub(crate) fn signatures_mut(&mut self) -> &mut Option<Vec<AgentCardSignature<'a>>> {
+        &mut self.signatures
+    }
+}
+
+/// Builds an [AgentCard], checking at [AgentCardBuilder::build] that the required fields are set.
+#[derive(Debug, Clone)]
+pub struct AgentCardBuilder<'a> {
+    card: AgentCard<'a>,
+}
+
+impl<'a> AgentCardBuilder<'a> {
+    pub fn new() -> Self {
+        Self {
+            card: AgentCard {
+                protocol_version: AgentCard::PROTOCOL_VERSION.into(),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn protocol_version(mut self, protocol_version: impl Into<Cow<'a, str>>) -> Self {
+        self.card.protocol_version = protocol_version.into();
+
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.card.name = name.into();
+
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<Cow<'a, str>>) -> Self {
+        self.card.description = description.into();
+
+        self
+    }
+
+    pub fn url(mut self, url: impl Into<Cow<'a, str>>) -> Self {
+        self.card.url = url.into();
+
+        self
+    }
+
+    pub fn preferred_transport(mut self, transport: TransportProtocol) -> Self {
+        self.card.preferred_transport = Some(transport);
+
+        self
+    }
+
+    /// Add an interface to `additionalInterfaces`.
+    pub fn additional_interface(mut self, interface: AgentInterface<'a>) -> Self {
+        self.card
+            .additional_interfaces
+            .get_or_insert_with(Vec::new)
+            .push(interface);
+
+        self
+    }
+
+    pub fn icon_url(mut self, icon_url: impl Into<Cow<'a, str>>) -> Self {
+        self.card.icon_url = Some(icon_url.into());
+
+        self
+    }
+
+    pub fn provider(mut self, provider: AgentProvider<'a>) -> Self {
+        self.card.provider = Some(provider);
+
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<Cow<'a, str>>) -> Self {
+        self.card.version = version.into();
+
+        self
+    }
+
+    pub fn documentation_url(mut self, documentation_url: impl Into<Cow<'a, str>>) -> Self {
+        self.card.documentation_url = Some(documentation_url.into());
+
+        self
+    }
+
+    pub fn capabilities(mut self, capabilities: AgentCapabilities<'a>) -> Self {
+        self.card.capabilities = capabilities;
+
+        self
+    }
+
+    /// Add a security scheme under `name` to `securitySchemes`.
+    pub fn security_scheme(
+        mut self,
+        name: impl Into<Cow<'a, str>>,
+        scheme: SecurityScheme<'a>,
+    ) -> Self {
+        self.card
+            .security_schemes
+            .get_or_insert_with(BTreeMap::new)
+            .insert(name.into(), scheme);
+
+        self
+    }
+
+    /// Add a requirement to `security`. The requirements are alternatives to each other.
+    pub fn security(mut self, requirement: SecurityRequirement<'a>) -> Self {
+        self.card
+            .security
+            .get_or_insert_with(Vec::new)
+            .push(requirement);
+
+        self
+    }
+
+    pub fn default_input_modes<I>(mut self, modes: I) -> Self
+    where
+        I: IntoIterator<Item = MediaType<'a>>,
+    {
+        self.card.default_input_modes = modes.into_iter().collect();
+
+        self
+    }
+
+    pub fn default_output_modes<I>(mut self, modes: I) -> Self
+    where
+        I: IntoIterator<Item = MediaType<'a>>,
+    {
+        self.card.default_output_modes = modes.into_iter().collect();
+
+        self
+    }
+
+    pub fn skill(mut self, skill: AgentSkill<'a>) -> Self {
+        self.card.skills.push(skill);
+
+        self
+    }
+
+    pub fn supports_authenticated_extended_card(mut self, supported: bool) -> Self {
+        self.card.supports_authenticated_extended_card = Some(supported);
+
+        self
+    }
+
+    pub fn signature(mut self, signature: AgentCardSignature<'a>) -> Self {
+        self.card
+            .signatures
+            .get_or_insert_with(Vec::new)
+            .push(signature);
+
+        self
+    }
+
+    pub fn build(self) -> Result<AgentCard<'a>, MissingFieldError> {
+        let card = self.card;
+
+        MissingFieldError::check(
+            "AgentCard",
+            [
+                ("protocolVersion", !card.protocol_version.is_empty()),
+                ("name", !card.name.is_empty()),
+                ("description", !card.description.is_empty()),
+                ("url", !card.url.is_empty()),
+                ("version", !card.version.is_empty()),
+                ("defaultInputModes", !card.default_input_modes.is_empty()),
+                ("defaultOutputModes", !card.default_output_modes.is_empty()),
+                ("skills", !card.skills.is_empty()),
+            ],
+        )?;
+
+        Ok(card)
+    }
+}
+
+impl<'a> Default for AgentCardBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Information about the organization or entity providing the agent.
@@ -171,9 +399,11 @@ impl<'a> AgentCard<'a> {
 #[serde(rename_all = "camelCase")]
 pub struct AgentProvider<'a> {
     /// The name of the agent provider's organization.
-    pub organization: &'a str,
+    #[serde(borrow)]
+    pub organization: Cow<'a, str>,
     /// A URL for the agent provider's website or relevant documentation.
-    pub url: &'a str,
+    #[serde(borrow)]
+    pub url: Cow<'a, str>,
 }
 
 /// Specifies optional A2A protocol features supported by the agent.
@@ -212,7 +442,7 @@ pub struct AgentExtension<'a> {
     /// Optional, extension-specific configuration parameters.
     #[serde(borrow)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub params: Option<BTreeMap<String, JsonStr<'a>>>,
+    pub params: Option<JsonObject<'a>>,
 }
 
 pub fn serialize_transport_optional<S>(
@@ -234,8 +464,8 @@ pub fn deserialize_transport_optional<'de, D>(
 where
     D: Deserializer<'de>,
 {
-    let opt = Option::<&'de str>::deserialize(deserializer)?;
-    Ok(opt.map(|value| value.into()))
+    let opt = Option::<Cow<'de, str>>::deserialize(deserializer)?;
+    Ok(opt.map(|value| value.as_ref().into()))
 }
 
 pub fn serialize_additional_interfaces<S>(
@@ -251,3 +481,37 @@ where
         serializer.serialize_some(additional_interfaces)
     }
 }
+
+impl_into_owned!(AgentCard {
+    protocol_version,
+    name,
+    description,
+    url,
+    preferred_transport,
+    additional_interfaces,
+    icon_url,
+    provider,
+    version,
+    documentation_url,
+    capabilities,
+    security_schemes,
+    security,
+    default_input_modes,
+    default_output_modes,
+    skills,
+    supports_authenticated_extended_card,
+    signatures,
+});
+impl_into_owned!(AgentProvider { organization, url });
+impl_into_owned!(AgentCapabilities {
+    streaming,
+    push_notifications,
+    state_transition_history,
+    extensions,
+});
+impl_into_owned!(AgentExtension {
+    uri,
+    description,
+    required,
+    params,
+});
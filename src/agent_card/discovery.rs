@@ -0,0 +1,545 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Write},
+    net::TcpStream,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use serde_json::value::RawValue;
+
+use crate::{
+    jsonrpc::{
+        A2aMethod, A2aResponse, AgentGetAuthenticatedExtendedCard, JsonRpcId, JsonRpcPayload,
+    },
+    AgentCard, DiscoveryError, IntoOwned, TransportProtocol,
+};
+#[cfg(feature = "jws")]
+use crate::{verify_card_signatures, KeyResolver};
+
+/// The path of the agent card of a domain (RFC 8615).
+pub const AGENT_CARD_PATH: &str = "/.well-known/agent-card.json";
+
+/// The path used before version 0.3.0 of the protocol, tried when [AGENT_CARD_PATH] is not found.
+pub const LEGACY_AGENT_CARD_PATH: &str = "/.well-known/agent.json";
+
+/// An HTTP request sent by [CardDiscovery].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct HttpRequest {
+    /// `GET` or `POST`.
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+/// The response to an [HttpRequest].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// The value of the first header named `name`, ignoring ASCII case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Sends the requests of [CardDiscovery].
+///
+/// Implemented for an HTTPS client by the application, [PlainHttpClient] only speaks plain `http`.
+pub trait HttpClient {
+    fn send(&self, request: &HttpRequest) -> io::Result<HttpResponse>;
+}
+
+/// A blocking HTTP/1.1 client for `http://` URLs, one connection per request.
+///
+/// Meant for agents on `localhost` or a test network, the way [crate::CardValidator] has a dev mode.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct PlainHttpClient {
+    timeout: Option<Duration>,
+}
+
+impl PlainHttpClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail a request when connecting, a read or a write takes longer than `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+
+        self
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn connect(&self, address: &str) -> io::Result<TcpStream> {
+        let stream = match self.timeout {
+            Some(timeout) => {
+                let mut last_error = None;
+                let mut stream = None;
+
+                for address in std::net::ToSocketAddrs::to_socket_addrs(address)? {
+                    match TcpStream::connect_timeout(&address, timeout) {
+                        Ok(connected) => {
+                            stream = Some(connected);
+                            break;
+                        }
+                        Err(error) => last_error = Some(error),
+                    }
+                }
+
+                stream.ok_or_else(|| {
+                    last_error.unwrap_or_else(|| io::Error::other("the host has no address"))
+                })?
+            }
+            None => TcpStream::connect(address)?,
+        };
+
+        stream.set_read_timeout(self.timeout)?;
+        stream.set_write_timeout(self.timeout)?;
+
+        Ok(stream)
+    }
+}
+
+impl HttpClient for PlainHttpClient {
+    fn send(&self, request: &HttpRequest) -> io::Result<HttpResponse> {
+        let invalid = |reason| io::Error::new(io::ErrorKind::InvalidInput, reason);
+
+        let rest = request
+            .url
+            .get(..7)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("http://"))
+            .map(|_| &request.url[7..])
+            .ok_or_else(|| invalid("only http:// URLs are supported"))?;
+        let (authority, path) = rest.split_at(rest.find(['/', '?']).unwrap_or(rest.len()));
+        let path = match path.starts_with('/') {
+            true => path.to_owned(),
+            false => format!("/{path}"),
+        };
+        if authority.is_empty() {
+            return Err(invalid("the URL has no host"));
+        }
+
+        let address = match authority.rsplit_once(':') {
+            Some((_, port)) if !port.contains(']') => authority.to_owned(),
+            _ => format!("{authority}:80"),
+        };
+
+        let mut head = format!(
+            "{} {path} HTTP/1.1\r\nHost: {authority}\r\nConnection: close\r\n",
+            request.method
+        );
+        for (name, value) in &request.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        if let Some(body) = &request.body {
+            head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+        }
+        head.push_str("\r\n");
+
+        let mut stream = self.connect(&address)?;
+        stream.write_all(head.as_bytes())?;
+        if let Some(body) = &request.body {
+            stream.write_all(body)?;
+        }
+
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw)?;
+
+        parse_response(&raw)
+    }
+}
+
+/// Fetches the agent card of a domain and keeps it as long as its `Cache-Control` allows.
+///
+/// The card is looked up at [AGENT_CARD_PATH] of the origin of the URL, then at [LEGACY_AGENT_CARD_PATH].
+/// A cached card that is no longer fresh is revalidated with its `ETag`.
+/// `Expires` is not read, a response without `max-age` is revalidated every time.
+///
+/// When an authorization is set and the card has `supportsAuthenticatedExtendedCard`,
+/// the extended card is requested with `agent/getAuthenticatedExtendedCard`. It is never cached,
+/// and failing to get it does not fail the discovery, see [DiscoveredCard::extended_card].
+pub struct CardDiscovery<C> {
+    client: C,
+    authorization: Vec<(String, String)>,
+    #[cfg(feature = "jws")]
+    key_resolver: Option<Box<dyn KeyResolver + Send + Sync>>,
+    cache: Mutex<BTreeMap<String, CachedCard>>,
+}
+
+/// An agent card found by [CardDiscovery].
+#[derive(Debug)]
+pub struct DiscoveredCard {
+    /// Where the card was found.
+    pub url: String,
+    pub card: AgentCard<'static>,
+    /// The card of `agent/getAuthenticatedExtendedCard`, or why it could not be fetched.
+    /// `None` when it was not requested.
+    pub extended_card: Option<Result<AgentCard<'static>, DiscoveryError>>,
+    /// The card comes from the cache, with or without a revalidation.
+    pub cached: bool,
+}
+
+/// The `result` of a JSON-RPC response as it was received.
+#[derive(Deserialize)]
+struct RawResult<'a> {
+    #[serde(borrow)]
+    result: Option<&'a RawValue>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedCard {
+    card: AgentCard<'static>,
+    etag: Option<String>,
+    fresh_until: Instant,
+}
+
+impl<C: HttpClient> CardDiscovery<C> {
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            authorization: Vec::new(),
+            #[cfg(feature = "jws")]
+            key_resolver: None,
+            cache: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Send `name: value` with the `agent/getAuthenticatedExtendedCard` request,
+    /// e.g. `Authorization: Bearer <token>`.
+    ///
+    /// The request goes to the JSON-RPC interface declared by the card, so the credentials are only
+    /// sent when that interface has the origin the card was discovered at. Otherwise the extended card
+    /// fails with [DiscoveryError::UntrustedExtendedCardUrl]. Discovering an `http` origin sends them
+    /// in clear text.
+    pub fn with_authorization(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.authorization.push((name.into(), value.into()));
+
+        self
+    }
+
+    /// Reject the cards that have no signature valid for the keys of `resolver`.
+    #[cfg(feature = "jws")]
+    pub fn with_key_resolver(mut self, resolver: impl KeyResolver + Send + Sync + 'static) -> Self {
+        self.key_resolver = Some(Box::new(resolver));
+
+        self
+    }
+
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Forget the cached cards.
+    pub fn clear_cache(&self) {
+        self.cache().clear();
+    }
+
+    /// Find the card of `base`, a domain such as `example.com` or a URL whose origin serves the card.
+    /// A domain without a scheme is reached with `https`.
+    pub fn discover(&self, base: &str) -> Result<DiscoveredCard, DiscoveryError> {
+        let origin = origin(base)?;
+
+        let mut discovered = None;
+        for path in [AGENT_CARD_PATH, LEGACY_AGENT_CARD_PATH] {
+            let url = format!("{origin}{path}");
+
+            if let Some((card, cached)) = self.fetch_card(&url)? {
+                discovered = Some(DiscoveredCard {
+                    url,
+                    card,
+                    extended_card: None,
+                    cached,
+                });
+                break;
+            }
+        }
+        let mut discovered = discovered.ok_or_else(|| DiscoveryError::NotFound(origin.clone()))?;
+
+        if !self.authorization.is_empty()
+            && discovered.card.supports_authenticated_extended_card() == Some(true)
+        {
+            discovered.extended_card = Some(self.fetch_extended_card(&origin, &discovered.card));
+        }
+
+        Ok(discovered)
+    }
+
+    /// The card at `url`, `None` when there is no card there.
+    fn fetch_card(&self, url: &str) -> Result<Option<(AgentCard<'static>, bool)>, DiscoveryError> {
+        let cached = self.cache().get(url).cloned();
+
+        if let Some(cached) = cached
+            .as_ref()
+            .filter(|cached| cached.fresh_until > Instant::now())
+        {
+            return Ok(Some((cached.card.clone(), true)));
+        }
+
+        let etag = cached.as_ref().and_then(|cached| cached.etag.clone());
+
+        let mut headers = vec![("Accept".to_owned(), "application/json".to_owned())];
+        if let Some(etag) = &etag {
+            headers.push(("If-None-Match".to_owned(), etag.clone()));
+        }
+
+        let response = self.client.send(&HttpRequest {
+            method: "GET",
+            url: url.to_owned(),
+            headers,
+            body: None,
+        })?;
+
+        let (card, revalidated) = match (response.status, cached) {
+            (304, Some(cached)) => (cached.card, true),
+            (404 | 410, _) => {
+                self.cache().remove(url);
+                return Ok(None);
+            }
+            (200..=299, _) => {
+                self.verify(&response.body)?;
+                let card =
+                    IntoOwned::into_owned(serde_json::from_slice::<AgentCard>(&response.body)?);
+
+                (card, false)
+            }
+            (status, _) => {
+                return Err(DiscoveryError::Status {
+                    url: url.to_owned(),
+                    status,
+                })
+            }
+        };
+
+        match CacheControl::parse(&response) {
+            CacheControl::NoStore => {
+                self.cache().remove(url);
+            }
+            CacheControl::MaxAge(max_age) => {
+                let etag = match response.header("ETag") {
+                    Some(etag) => Some(etag.to_owned()),
+                    // A 304 does not have to repeat the ETag.
+                    None if revalidated => etag,
+                    None => None,
+                };
+
+                self.cache().insert(
+                    url.to_owned(),
+                    CachedCard {
+                        card: card.clone(),
+                        etag,
+                        fresh_until: Instant::now() + max_age,
+                    },
+                );
+            }
+        }
+
+        Ok(Some((card, revalidated)))
+    }
+
+    /// Request the extended card of `card`, found at `origin`.
+    fn fetch_extended_card(
+        &self,
+        origin: &str,
+        card: &AgentCard<'_>,
+    ) -> Result<AgentCard<'static>, DiscoveryError> {
+        let url = card
+            .select_interfaces(&[TransportProtocol::JsonRpc])
+            .into_iter()
+            .next()
+            .ok_or(DiscoveryError::NoJsonRpcInterface)?
+            .url
+            .into_owned();
+
+        // The authorization is meant for the origin that was discovered, not for any URL in the card.
+        if !self::origin(&url).is_ok_and(|url_origin| url_origin.eq_ignore_ascii_case(origin)) {
+            return Err(DiscoveryError::UntrustedExtendedCardUrl(url));
+        }
+
+        let request = AgentGetAuthenticatedExtendedCard::request((), JsonRpcId::Number(1));
+        let mut headers = vec![
+            ("Content-Type".to_owned(), "application/json".to_owned()),
+            ("Accept".to_owned(), "application/json".to_owned()),
+        ];
+        headers.extend(self.authorization.iter().cloned());
+
+        let response = self.client.send(&HttpRequest {
+            method: "POST",
+            url: url.clone(),
+            headers,
+            body: Some(serde_json::to_vec(&request)?),
+        })?;
+        if !(200..=299).contains(&response.status) {
+            return Err(DiscoveryError::Status {
+                url,
+                status: response.status,
+            });
+        }
+
+        if let Some(result) = serde_json::from_slice::<RawResult>(&response.body)?.result {
+            self.verify(result.get().as_bytes())?;
+        }
+        let response = serde_json::from_slice::<A2aResponse<AgentGetAuthenticatedExtendedCard>>(
+            &response.body,
+        )?;
+
+        match response.payload {
+            JsonRpcPayload::Success { result } => Ok(IntoOwned::into_owned(result)),
+            JsonRpcPayload::Error { error } => {
+                Err(DiscoveryError::JsonRpc(IntoOwned::into_owned(error)))
+            }
+        }
+    }
+
+    /// Check the signatures of the card `json` as it was received, see [verify_card_signatures].
+    #[cfg(feature = "jws")]
+    fn verify(&self, json: &[u8]) -> Result<(), DiscoveryError> {
+        if let Some(resolver) = &self.key_resolver {
+            verify_card_signatures(json, resolver.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "jws"))]
+    fn verify(&self, _json: &[u8]) -> Result<(), DiscoveryError> {
+        Ok(())
+    }
+
+    fn cache(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, CachedCard>> {
+        self.cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<C: std::fmt::Debug> std::fmt::Debug for CardDiscovery<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CardDiscovery")
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
+}
+
+/// How long a response may be used without revalidation.
+enum CacheControl {
+    NoStore,
+    MaxAge(Duration),
+}
+
+impl CacheControl {
+    fn parse(response: &HttpResponse) -> Self {
+        let mut max_age = Duration::ZERO;
+
+        for directive in response
+            .header("Cache-Control")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+        {
+            let (name, value) = directive.split_once('=').unwrap_or((directive, ""));
+
+            if name.eq_ignore_ascii_case("no-store") {
+                return Self::NoStore;
+            } else if name.eq_ignore_ascii_case("no-cache") {
+                return Self::MaxAge(Duration::ZERO);
+            } else if name.eq_ignore_ascii_case("max-age") {
+                max_age = Duration::from_secs(value.trim_matches('"').parse().unwrap_or_default());
+            }
+        }
+
+        let age = response
+            .header("Age")
+            .and_then(|age| age.trim().parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or_default();
+
+        Self::MaxAge(max_age.saturating_sub(age))
+    }
+}
+
+/// `scheme://authority` of `base`, with `https` when there is no scheme.
+fn origin(base: &str) -> Result<String, DiscoveryError> {
+    let base = base.trim();
+    let (scheme, rest) = base.split_once("://").unwrap_or(("https", base));
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+
+    if authority.is_empty() {
+        return Err(DiscoveryError::InvalidUrl(base.to_owned()));
+    }
+
+    Ok(format!("{}://{authority}", scheme.to_ascii_lowercase()))
+}
+
+fn parse_response(raw: &[u8]) -> io::Result<HttpResponse> {
+    let invalid = |reason| io::Error::new(io::ErrorKind::InvalidData, reason);
+
+    let end = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(|| invalid("the response has no end of headers"))?;
+    let head =
+        std::str::from_utf8(&raw[..end]).map_err(|_| invalid("the headers are not UTF-8"))?;
+    let mut body = raw[end + 4..].to_vec();
+
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|line| line.split(' ').nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| invalid("the status line is malformed"))?;
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_owned(), value.trim().to_owned()))
+        .collect::<Vec<_>>();
+
+    let response = HttpResponse {
+        status,
+        headers,
+        body: Vec::new(),
+    };
+
+    if response
+        .header("Transfer-Encoding")
+        .is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked"))
+    {
+        body = decode_chunked(&body).ok_or_else(|| invalid("the chunked body is malformed"))?;
+    } else if let Some(length) = response
+        .header("Content-Length")
+        .and_then(|length| length.parse().ok())
+    {
+        body.truncate(length);
+    }
+
+    Ok(HttpResponse { body, ..response })
+}
+
+fn decode_chunked(mut raw: &[u8]) -> Option<Vec<u8>> {
+    let mut body = Vec::new();
+
+    loop {
+        let line_end = raw.windows(2).position(|window| window == b"\r\n")?;
+        let size = std::str::from_utf8(&raw[..line_end]).ok()?;
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        raw = &raw[line_end + 2..];
+
+        if size == 0 {
+            return Some(body);
+        }
+
+        body.extend_from_slice(raw.get(..size)?);
+        raw = raw.get(size + 2..)?;
+    }
+}
@@ -0,0 +1,543 @@
+use std::fmt;
+
+use ring::{
+    error::KeyRejected,
+    rand::SystemRandom,
+    signature::{
+        self, EcdsaKeyPair, Ed25519KeyPair, KeyPair, RsaKeyPair, RsaPublicKeyComponents,
+        UnparsedPublicKey,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    encode, to_canonical_vec, AgentCard, AgentCardSignature, CardSignatureError, FileDecoder,
+    FileFetcher,
+};
+
+/// The `typ` of the protected header of the signatures made by [AgentCard::sign].
+const JOSE_TYPE: &str = "JOSE";
+
+/// The JWS algorithms (RFC 7518) agent cards can be signed with.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum JwsAlgorithm {
+    /// ECDSA with the P-256 curve and SHA-256.
+    Es256,
+    /// Ed25519, as defined by RFC 8037.
+    EdDsa,
+    /// RSASSA-PKCS1-v1_5 with SHA-256.
+    Rs256,
+}
+
+impl JwsAlgorithm {
+    pub const ALL: [JwsAlgorithm; 3] = [Self::Es256, Self::EdDsa, Self::Rs256];
+
+    /// The `alg` of the JWS header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Es256 => "ES256",
+            Self::EdDsa => "EdDSA",
+            Self::Rs256 => "RS256",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.as_str() == name)
+    }
+
+    /// The `kty` and `crv` of the keys of the algorithm.
+    fn key_type(&self) -> (&'static str, Option<&'static str>) {
+        match self {
+            Self::Es256 => ("EC", Some("P-256")),
+            Self::EdDsa => ("OKP", Some("Ed25519")),
+            Self::Rs256 => ("RSA", None),
+        }
+    }
+}
+
+impl fmt::Display for JwsAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The public JSON Web Key (RFC 7517) of one of the [JwsAlgorithm]s.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default, Serialize, Deserialize)]
+pub struct Jwk {
+    /// `EC`, `OKP` or `RSA`.
+    pub kty: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    /// The curve of `EC` and `OKP` keys.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// The x coordinate of `EC` keys, or the public key of `OKP` keys.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    /// The y coordinate of `EC` keys.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    /// The modulus of `RSA` keys.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// The public exponent of `RSA` keys.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+}
+
+impl Jwk {
+    /// The key type fits `algorithm` and, when the key sets its `alg`, it is `algorithm`.
+    pub fn supports(&self, algorithm: JwsAlgorithm) -> bool {
+        let (kty, crv) = algorithm.key_type();
+
+        self.kty == kty
+            && self.crv.as_deref() == crv
+            && self
+                .alg
+                .as_deref()
+                .is_none_or(|alg| alg == algorithm.as_str())
+    }
+
+    fn verify(
+        &self,
+        algorithm: JwsAlgorithm,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), CardSignatureError> {
+        if !self.supports(algorithm) {
+            return Err(CardSignatureError::InvalidKey(
+                "the key does not fit the algorithm",
+            ));
+        }
+
+        let member = |value: &Option<String>, missing| {
+            value
+                .as_deref()
+                .and_then(decode_url)
+                .ok_or(CardSignatureError::InvalidKey(missing))
+        };
+
+        match algorithm {
+            JwsAlgorithm::Es256 => {
+                let mut point = vec![4];
+                point.extend(member(&self.x, "an EC key requires `x`")?);
+                point.extend(member(&self.y, "an EC key requires `y`")?);
+
+                UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_FIXED, point)
+                    .verify(message, signature)
+            }
+            JwsAlgorithm::EdDsa => UnparsedPublicKey::new(
+                &signature::ED25519,
+                member(&self.x, "an OKP key requires `x`")?,
+            )
+            .verify(message, signature),
+            JwsAlgorithm::Rs256 => RsaPublicKeyComponents {
+                n: member(&self.n, "an RSA key requires `n`")?,
+                e: member(&self.e, "an RSA key requires `e`")?,
+            }
+            .verify(&signature::RSA_PKCS1_2048_8192_SHA256, message, signature),
+        }
+        .map_err(|_| CardSignatureError::Mismatch)
+    }
+}
+
+/// A set of public keys (RFC 7517), as published at the `jku` of a signature.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default, Serialize, Deserialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+impl JwkSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_key(mut self, key: Jwk) -> Self {
+        self.keys.push(key);
+
+        self
+    }
+
+    /// The keys that can check a signature with `header`: the keys with its `kid`,
+    /// or every key of its algorithm when the header has no `kid`.
+    pub fn find<'s>(&'s self, header: &'s JwsHeader) -> impl Iterator<Item = &'s Jwk> {
+        let algorithm = JwsAlgorithm::from_name(&header.alg);
+
+        self.keys.iter().filter(move |key| {
+            algorithm.is_some_and(|algorithm| key.supports(algorithm))
+                && header
+                    .kid
+                    .as_ref()
+                    .is_none_or(|kid| key.kid.as_ref() == Some(kid))
+        })
+    }
+}
+
+/// The protected header of an [AgentCardSignature].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default, Serialize, Deserialize)]
+pub struct JwsHeader {
+    pub alg: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// The URL of the [JwkSet] holding the public key.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jku: Option<String>,
+    /// Extensions the verifier must understand. None is supported, so a signature listing any is rejected.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crit: Option<Vec<String>>,
+}
+
+impl JwsHeader {
+    pub fn algorithm(&self) -> Result<JwsAlgorithm, CardSignatureError> {
+        JwsAlgorithm::from_name(&self.alg)
+            .ok_or_else(|| CardSignatureError::UnsupportedAlgorithm(self.alg.clone()))
+    }
+}
+
+/// Finds the public keys that may have made a signature, see [verify_card_signatures].
+///
+/// A static [JwkSet] is a resolver, [JkuResolver] fetches the keys at the `jku` of the signature.
+pub trait KeyResolver {
+    fn resolve(&self, header: &JwsHeader) -> Result<Vec<Jwk>, CardSignatureError>;
+}
+
+impl KeyResolver for JwkSet {
+    fn resolve(&self, header: &JwsHeader) -> Result<Vec<Jwk>, CardSignatureError> {
+        Ok(self.find(header).cloned().collect())
+    }
+}
+
+/// Fetches the [JwkSet] at the `jku` of a signature with a [FileFetcher], usually an HTTP client.
+///
+/// The `jku` comes from the card being verified, so only URLs that start with a trusted prefix
+/// are fetched: otherwise a forged card could point to keys of its own.
+/// A resolver without trusted prefixes rejects every signature.
+#[derive(Debug, Clone)]
+pub struct JkuResolver<F> {
+    fetcher: F,
+    trusted_prefixes: Vec<String>,
+}
+
+impl<F: FileFetcher> JkuResolver<F> {
+    pub fn new(fetcher: F) -> Self {
+        Self {
+            fetcher,
+            trusted_prefixes: Vec::new(),
+        }
+    }
+
+    /// Trust the key sets whose URL starts with `prefix`, e.g. `https://example.com/.well-known/`.
+    pub fn with_trusted_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.trusted_prefixes.push(prefix.into());
+
+        self
+    }
+
+    pub fn trusted_prefixes(&self) -> &[String] {
+        &self.trusted_prefixes
+    }
+}
+
+impl<F: FileFetcher> KeyResolver for JkuResolver<F> {
+    fn resolve(&self, header: &JwsHeader) -> Result<Vec<Jwk>, CardSignatureError> {
+        let jku = header
+            .jku
+            .as_deref()
+            .ok_or_else(|| CardSignatureError::KeyNotFound(header.kid.clone()))?;
+
+        if !self
+            .trusted_prefixes
+            .iter()
+            .any(|prefix| jku.starts_with(prefix.as_str()))
+        {
+            return Err(CardSignatureError::UntrustedJwkSetUrl(jku.to_owned()));
+        }
+
+        let keys = serde_json::from_slice::<JwkSet>(&self.fetcher.fetch(jku)?.content)?;
+
+        keys.resolve(header)
+    }
+}
+
+/// A private key that signs agent cards, see [AgentCard::sign].
+pub struct SigningKey {
+    algorithm: JwsAlgorithm,
+    key_pair: SigningKeyPair,
+    key_id: Option<String>,
+    jwk_set_url: Option<String>,
+}
+
+enum SigningKeyPair {
+    Ecdsa(EcdsaKeyPair),
+    Ed25519(Ed25519KeyPair),
+    Rsa(RsaKeyPair),
+}
+
+impl SigningKey {
+    /// Load a PKCS#8 DER private key of `algorithm`: a P-256, an Ed25519 or an RSA key.
+    pub fn from_pkcs8(algorithm: JwsAlgorithm, pkcs8: &[u8]) -> Result<Self, CardSignatureError> {
+        let rejected = |_: KeyRejected| {
+            CardSignatureError::InvalidKey("not a PKCS#8 private key of the algorithm")
+        };
+
+        let key_pair = match algorithm {
+            JwsAlgorithm::Es256 => SigningKeyPair::Ecdsa(
+                EcdsaKeyPair::from_pkcs8(
+                    &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+                    pkcs8,
+                    &SystemRandom::new(),
+                )
+                .map_err(rejected)?,
+            ),
+            JwsAlgorithm::EdDsa => SigningKeyPair::Ed25519(
+                Ed25519KeyPair::from_pkcs8_maybe_unchecked(pkcs8).map_err(rejected)?,
+            ),
+            JwsAlgorithm::Rs256 => {
+                SigningKeyPair::Rsa(RsaKeyPair::from_pkcs8(pkcs8).map_err(rejected)?)
+            }
+        };
+
+        Ok(Self {
+            algorithm,
+            key_pair,
+            key_id: None,
+            jwk_set_url: None,
+        })
+    }
+
+    /// Set the `kid` of the signatures and of [SigningKey::public_jwk].
+    pub fn with_key_id(mut self, key_id: impl Into<String>) -> Self {
+        self.key_id = Some(key_id.into());
+
+        self
+    }
+
+    /// Set the `jku` of the signatures, the URL where the [JwkSet] with [SigningKey::public_jwk] is published.
+    pub fn with_jwk_set_url(mut self, jwk_set_url: impl Into<String>) -> Self {
+        self.jwk_set_url = Some(jwk_set_url.into());
+
+        self
+    }
+
+    pub fn algorithm(&self) -> JwsAlgorithm {
+        self.algorithm
+    }
+
+    pub fn key_id(&self) -> Option<&str> {
+        self.key_id.as_deref()
+    }
+
+    pub fn jwk_set_url(&self) -> Option<&str> {
+        self.jwk_set_url.as_deref()
+    }
+
+    /// The public key, to publish in the [JwkSet] of the agent.
+    pub fn public_jwk(&self) -> Jwk {
+        let (kty, crv) = self.algorithm.key_type();
+        let mut jwk = Jwk {
+            kty: kty.to_owned(),
+            kid: self.key_id.clone(),
+            alg: Some(self.algorithm.as_str().to_owned()),
+            crv: crv.map(str::to_owned),
+            ..Jwk::default()
+        };
+
+        match &self.key_pair {
+            SigningKeyPair::Ecdsa(key_pair) => {
+                // An uncompressed point: 0x04, then x and y.
+                let point = key_pair.public_key().as_ref();
+                jwk.x = Some(encode_url(&point[1..33]));
+                jwk.y = Some(encode_url(&point[33..]));
+            }
+            SigningKeyPair::Ed25519(key_pair) => {
+                jwk.x = Some(encode_url(key_pair.public_key().as_ref()));
+            }
+            SigningKeyPair::Rsa(key_pair) => {
+                let components = RsaPublicKeyComponents::<Vec<u8>>::from(key_pair.public());
+                jwk.n = Some(encode_url(&components.n));
+                jwk.e = Some(encode_url(&components.e));
+            }
+        }
+
+        jwk
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, CardSignatureError> {
+        let random = SystemRandom::new();
+
+        match &self.key_pair {
+            SigningKeyPair::Ecdsa(key_pair) => key_pair
+                .sign(&random, message)
+                .map(|signature| signature.as_ref().to_vec()),
+            SigningKeyPair::Ed25519(key_pair) => Ok(key_pair.sign(message).as_ref().to_vec()),
+            SigningKeyPair::Rsa(key_pair) => {
+                let mut signature = vec![0; key_pair.public().modulus_len()];
+
+                key_pair
+                    .sign(
+                        &signature::RSA_PKCS1_SHA256,
+                        &random,
+                        message,
+                        &mut signature,
+                    )
+                    .map(|()| signature)
+            }
+        }
+        .map_err(|_| CardSignatureError::SigningFailed)
+    }
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("algorithm", &self.algorithm)
+            .field("key_id", &self.key_id)
+            .field("jwk_set_url", &self.jwk_set_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> AgentCard<'a> {
+    /// The bytes [AgentCard::sign] signs: the card without `signatures`, canonicalized with RFC 8785,
+    /// see [to_canonical_vec]. A received card is verified over its JSON, see [verify_card_signatures].
+    pub fn signing_payload(&self) -> Result<Vec<u8>, CardSignatureError> {
+        let mut card = self.clone();
+        *card.signatures_mut() = None;
+
+        Ok(to_canonical_vec(&card)?)
+    }
+
+    /// Add a JWS signature of the card to `signatures`. The payload is detached
+    /// (RFC 7515 appendix F): a verifier computes it again from the card.
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), CardSignatureError> {
+        let header = JwsHeader {
+            alg: key.algorithm.as_str().to_owned(),
+            typ: Some(JOSE_TYPE.to_owned()),
+            kid: key.key_id.clone(),
+            jku: key.jwk_set_url.clone(),
+            crit: None,
+        };
+        let protected = encode_url(&serde_json::to_vec(&header)?);
+        let signature = key.sign(signing_input(&protected, &self.signing_payload()?).as_bytes())?;
+
+        self.signatures_mut()
+            .get_or_insert_with(Vec::new)
+            .push(AgentCardSignature {
+                protected: protected.into(),
+                signature: encode_url(&signature).into(),
+                header: None,
+            });
+
+        Ok(())
+    }
+}
+
+/// Check the signatures of the agent card `json`, as it was received, with the keys found by `resolver`
+/// and return the header of the first valid one.
+///
+/// The payload is the received card without `signatures`, canonicalized with RFC 8785, so the members
+/// [AgentCard] does not model, e.g. extensions, and explicit `null`s are signed too.
+/// Only the protected header is read, the unprotected `header` of a signature is ignored.
+/// When no signature is valid the error is the one of the first signature.
+pub fn verify_card_signatures(
+    json: &[u8],
+    resolver: &(impl KeyResolver + ?Sized),
+) -> Result<JwsHeader, CardSignatureError> {
+    let mut card = serde_json::from_slice::<Map<String, Value>>(json)?;
+    let signatures = card.remove("signatures").unwrap_or_default();
+    let signatures = Option::<Vec<AgentCardSignature>>::deserialize(&signatures)?
+        .filter(|signatures| !signatures.is_empty())
+        .ok_or(CardSignatureError::Unsigned)?;
+    let payload = to_canonical_vec(&card)?;
+
+    let mut first_error = None;
+
+    for signature in &signatures {
+        match verify(signature, &payload, resolver) {
+            Ok(header) => return Ok(header),
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+
+    Err(first_error.unwrap_or(CardSignatureError::Unsigned))
+}
+
+fn verify(
+    signature: &AgentCardSignature<'_>,
+    payload: &[u8],
+    resolver: &(impl KeyResolver + ?Sized),
+) -> Result<JwsHeader, CardSignatureError> {
+    let header = decode_url(&signature.protected).ok_or(CardSignatureError::Malformed(
+        "the protected header is not base64url",
+    ))?;
+    let header = serde_json::from_slice::<JwsHeader>(&header)?;
+    let algorithm = header.algorithm()?;
+
+    if let Some(crit) = header.crit.as_ref().filter(|crit| !crit.is_empty()) {
+        return Err(CardSignatureError::UnsupportedCritical(crit.clone()));
+    }
+
+    let bytes = decode_url(&signature.signature).ok_or(CardSignatureError::Malformed(
+        "the signature is not base64url",
+    ))?;
+    let message = signing_input(&signature.protected, payload);
+
+    let keys = resolver.resolve(&header)?;
+    if keys.is_empty() {
+        return Err(CardSignatureError::KeyNotFound(header.kid));
+    }
+
+    match keys
+        .iter()
+        .any(|key| key.verify(algorithm, message.as_bytes(), &bytes).is_ok())
+    {
+        true => Ok(header),
+        false => Err(CardSignatureError::Mismatch),
+    }
+}
+
+/// `BASE64URL(protected header) || '.' || BASE64URL(payload)`
+fn signing_input(protected: &str, payload: &[u8]) -> String {
+    format!("{protected}.{}", encode_url(payload))
+}
+
+/// Base64url without padding, as used by JWS.
+fn encode_url(bytes: &[u8]) -> String {
+    encode(bytes)
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+fn decode_url(encoded: &str) -> Option<Vec<u8>> {
+    if encoded.contains(['+', '/', '=']) {
+        return None;
+    }
+
+    let mut standard = encoded.replace('-', "+").replace('_', "/");
+    while !standard.len().is_multiple_of(4) {
+        standard.push('=');
+    }
+
+    FileDecoder::new().decode_base64(&standard).ok()
+}
@@ -15,3 +15,17 @@ pub use skill::*;
 
 mod security_scheme;
 pub use security_scheme::*;
+
+mod validation;
+pub use validation::*;
+
+mod selection;
+pub use selection::*;
+
+#[cfg(feature = "jws")]
+mod jws;
+#[cfg(feature = "jws")]
+pub use jws::*;
+
+mod discovery;
+pub use discovery::*;
@@ -1,8 +1,12 @@
-use std::collections::BTreeMap;
+use std::{borrow::Cow, collections::BTreeMap};
 
 use serde::{Deserialize, Serialize};
 
-use crate::OpenIdExtension;
+use crate::{IntoOwned, OpenIdExtension};
+
+/// A Security Requirement Object: the names of the security schemes that must all be used,
+/// each with the scopes it needs. Follows the OpenAPI 3.0 Security Requirement Object.
+pub type SecurityRequirement<'a> = BTreeMap<Cow<'a, str>, Vec<Cow<'a, str>>>;
 
 /// Describes the authentication requirements for accessing the agent's url endpoint. Refer Sample Agent Card for an example.
 /// Defines a security scheme that can be used to secure an agent's endpoints.
@@ -129,3 +133,61 @@ pub struct OAuthFlow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scopes: Option<BTreeMap<String, String>>,
 }
+
+impl<'a> IntoOwned for SecurityScheme<'a> {
+    type Owned = SecurityScheme<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Self::ApiKey {
+                name,
+                location,
+                description,
+                extensions,
+            } => SecurityScheme::ApiKey {
+                name,
+                location,
+                description,
+                extensions: extensions.into_owned(),
+            },
+            Self::Http {
+                scheme,
+                bearer_format,
+                description,
+                extensions,
+            } => SecurityScheme::Http {
+                scheme,
+                bearer_format,
+                description,
+                extensions: extensions.into_owned(),
+            },
+            Self::MutualTLS {
+                description,
+                extensions,
+            } => SecurityScheme::MutualTLS {
+                description,
+                extensions: extensions.into_owned(),
+            },
+            Self::OAuth2 {
+                flows,
+                oauth2_metadata_url,
+                description,
+                extensions,
+            } => SecurityScheme::OAuth2 {
+                flows,
+                oauth2_metadata_url,
+                description,
+                extensions: extensions.into_owned(),
+            },
+            Self::OpenIdConnect {
+                open_id_connect_url,
+                description,
+                extensions,
+            } => SecurityScheme::OpenIdConnect {
+                open_id_connect_url,
+                description,
+                extensions: extensions.into_owned(),
+            },
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::{borrow::Cow, iter};
+
+use crate::{AgentCard, AgentInterface, ConnectError, IntoOwned, TransportProtocol};
+
+impl<'a> AgentCard<'a> {
+    /// The interfaces a client supporting the `supported` transports can use, in the order it
+    /// should try them: the main `url` with the `preferredTransport`, JSON-RPC when it is absent,
+    /// then the `additionalInterfaces` in the order of the card.
+    ///
+    /// An interface the card lists again only appears once. The order of `supported` is ignored,
+    /// the card states the preference of the agent. An interface with a transport this crate does not
+    /// know, see [TransportProtocol::Other], is only selected when `supported` names it.
+    pub fn select_interfaces(&self, supported: &[TransportProtocol]) -> Vec<AgentInterface<'_>> {
+        let preferred = AgentInterface {
+            url: Cow::Borrowed(self.url()),
+            transport: self.preferred_transport().cloned().unwrap_or_default(),
+        };
+        let additional = self
+            .additional_interfaces()
+            .into_iter()
+            .flatten()
+            .map(|interface| AgentInterface {
+                url: Cow::Borrowed(interface.url.as_ref()),
+                transport: interface.transport.clone(),
+            });
+
+        let mut selected = Vec::new();
+        for interface in iter::once(preferred).chain(additional) {
+            if supported.contains(&interface.transport) && !selected.contains(&interface) {
+                selected.push(interface);
+            }
+        }
+
+        selected
+    }
+
+    /// Connect to the first of the [AgentCard::select_interfaces] that `connect` succeeds with,
+    /// see [connect_with_fallback].
+    pub fn connect<T, E>(
+        &self,
+        supported: &[TransportProtocol],
+        connect: impl FnMut(&AgentInterface<'_>) -> Result<T, Fallback<E>>,
+    ) -> Result<T, ConnectError<E>> {
+        connect_with_fallback(self.select_interfaces(supported), connect)
+    }
+}
+
+/// What [connect_with_fallback] does after `connect` failed on an interface.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Fallback<E> {
+    /// Try the next interface, e.g. when the connection was refused.
+    Next(E),
+    /// Do not try the other interfaces, e.g. when the authorization was rejected
+    /// and would be rejected by every interface.
+    Stop(E),
+}
+
+impl<E> Fallback<E> {
+    pub fn into_inner(self) -> E {
+        match self {
+            Self::Next(error) | Self::Stop(error) => error,
+        }
+    }
+}
+
+/// Try `connect` on each interface in order and return the first connection.
+///
+/// An error wrapped in [Fallback::Next] moves on to the next interface,
+/// one wrapped in [Fallback::Stop] returns the failures so far.
+///
+/// ```
+/// use agentic_types::{connect_with_fallback, AgentInterface, Fallback, TransportProtocol};
+///
+/// let interfaces = [
+///     AgentInterface { url: "https://grpc.example.com/a2a".into(), transport: TransportProtocol::Grpc },
+///     AgentInterface { url: "https://api.example.com/a2a/v1".into(), transport: TransportProtocol::JsonRpc },
+/// ];
+///
+/// let error = connect_with_fallback(interfaces, |_| Err::<(), _>(Fallback::Stop("401 Unauthorized")))
+///     .unwrap_err();
+/// assert!(error.stopped);
+/// assert_eq!(error.failures.len(), 1);
+/// ```
+pub fn connect_with_fallback<'i, T, E>(
+    interfaces: impl IntoIterator<Item = AgentInterface<'i>>,
+    mut connect: impl FnMut(&AgentInterface<'i>) -> Result<T, Fallback<E>>,
+) -> Result<T, ConnectError<E>> {
+    let mut failures = Vec::new();
+
+    for interface in interfaces {
+        match connect(&interface) {
+            Ok(connection) => return Ok(connection),
+            Err(Fallback::Next(error)) => failures.push((IntoOwned::into_owned(interface), error)),
+            Err(Fallback::Stop(error)) => {
+                failures.push((IntoOwned::into_owned(interface), error));
+
+                return Err(ConnectError {
+                    failures,
+                    stopped: true,
+                });
+            }
+        }
+    }
+
+    Err(ConnectError {
+        failures,
+        stopped: false,
+    })
+}
@@ -1,8 +1,8 @@
-use std::collections::BTreeMap;
+use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 
-use crate::JsonStr;
+use crate::{impl_into_owned, JsonObject};
 
 /// Represents a JSON Web Signature (JWS) used to verify the integrity of the AgentCard.
 /// AgentCardSignature represents a JWS signature of an AgentCard.
@@ -11,11 +11,19 @@ use crate::JsonStr;
 pub struct AgentCardSignature<'a> {
     /// The protected JWS header for the signature.
     /// This is a Base64url-encoded JSON object, as per RFC 7515.
-    pub protected: &'a str,
+    #[serde(borrow)]
+    pub protected: Cow<'a, str>,
     /// The computed signature, Base64url-encoded.
     #[serde(borrow)]
-    pub signature: &'a str,
+    pub signature: Cow<'a, str>,
     /// The unprotected JWS header values.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub header: Option<BTreeMap<&'a str, JsonStr<'a>>>,
+    #[serde(borrow)]
+    pub header: Option<JsonObject<'a>>,
 }
+
+impl_into_owned!(AgentCardSignature {
+    protected,
+    signature,
+    header,
+});
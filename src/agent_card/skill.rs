@@ -1,7 +1,9 @@
-use std::{borrow::Cow, collections::BTreeMap};
+use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 
+use crate::{impl_into_owned, MediaType, MissingFieldError, SecurityRequirement};
+
 /// Describes a specific capability, function, or area of expertise the agent can perform or address.
 /// Represents a distinct capability or function that an agent can perform.
 
@@ -9,28 +11,169 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct AgentSkill<'a> {
     /// A unique identifier for the agent's skill.
-    id: &'a str,
+    #[serde(borrow)]
+    id: Cow<'a, str>,
     /// A human-readable name for the skill.
-    name: &'a str,
+    #[serde(borrow)]
+    name: Cow<'a, str>,
     /// A detailed description of the skill, intended to help clients or users understand its purpose and functionality.
-    description: &'a str,
+    #[serde(borrow)]
+    description: Cow<'a, str>,
     /// A set of keywords describing the skill's capabilities.
     /// examples:  vec!["cooking", "customer support", "billing"]
-    tags: Vec<&'a str>,
+    #[serde(borrow)]
+    tags: Vec<Cow<'a, str>>,
     /// Example prompts or scenarios that this skill can handle. Provides a hint to the client on how to use the skill.
     /// vec!["I need a recipe for bread"]
     #[serde(skip_serializing_if = "Option::is_none")]
     examples: Option<Vec<Cow<'a, str>>>,
     /// The set of supported input MIME types for this skill, overriding the agent's defaults.
     #[serde(skip_serializing_if = "Option::is_none")]
-    input_modes: Option<Vec<&'a str>>,
+    #[serde(borrow)]
+    input_modes: Option<Vec<MediaType<'a>>>,
     /// The set of supported output MIME types for this skill, overriding the agent's defaults.
     #[serde(skip_serializing_if = "Option::is_none")]
-    output_modes: Option<Vec<&'a str>>,
+    #[serde(borrow)]
+    output_modes: Option<Vec<MediaType<'a>>>,
     /// Security schemes necessary for the agent to leverage this skill.
     /// As in the overall AgentCard.security, this list represents a logical OR of security
     /// requirement objects. Each object is a set of security schemes that must be used together (a logical AND).
     /// examples [[{"google": ["oidc"]}]]
     #[serde(skip_serializing_if = "Option::is_none")]
-    security: Option<Vec<BTreeMap<&'a str, Vec<&'a str>>>>,
+    #[serde(borrow)]
+    security: Option<Vec<SecurityRequirement<'a>>>,
+}
+
+impl<'a> AgentSkill<'a> {
+    /// Start building a skill. `id`, `name` and `description` are required.
+    pub fn builder() -> AgentSkillBuilder<'a> {
+        AgentSkillBuilder::default()
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn tags(&self) -> &[Cow<'a, str>] {
+        self.tags.as_slice()
+    }
+
+    pub fn examples(&self) -> Option<&[Cow<'a, str>]> {
+        self.examples.as_deref()
+    }
+
+    pub fn input_modes(&self) -> Option<&[MediaType<'a>]> {
+        self.input_modes.as_deref()
+    }
+
+    pub fn output_modes(&self) -> Option<&[MediaType<'a>]> {
+        self.output_modes.as_deref()
+    }
+
+    pub fn security(&self) -> Option<&[SecurityRequirement<'a>]> {
+        self.security.as_deref()
+    }
+}
+
+/// Builds an [AgentSkill], checking at [AgentSkillBuilder::build] that the required fields are set.
+#[derive(Debug, Default, Clone)]
+pub struct AgentSkillBuilder<'a> {
+    skill: AgentSkill<'a>,
+}
+
+impl<'a> AgentSkillBuilder<'a> {
+    pub fn id(mut self, id: impl Into<Cow<'a, str>>) -> Self {
+        self.skill.id = id.into();
+
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.skill.name = name.into();
+
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<Cow<'a, str>>) -> Self {
+        self.skill.description = description.into();
+
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<Cow<'a, str>>) -> Self {
+        self.skill.tags.push(tag.into());
+
+        self
+    }
+
+    pub fn example(mut self, example: impl Into<Cow<'a, str>>) -> Self {
+        self.skill
+            .examples
+            .get_or_insert_with(Vec::new)
+            .push(example.into());
+
+        self
+    }
+
+    pub fn input_modes<I>(mut self, modes: I) -> Self
+    where
+        I: IntoIterator<Item = MediaType<'a>>,
+    {
+        self.skill.input_modes = Some(modes.into_iter().collect());
+
+        self
+    }
+
+    pub fn output_modes<I>(mut self, modes: I) -> Self
+    where
+        I: IntoIterator<Item = MediaType<'a>>,
+    {
+        self.skill.output_modes = Some(modes.into_iter().collect());
+
+        self
+    }
+
+    /// Add a requirement to `security`. The requirements are alternatives to each other.
+    pub fn security(mut self, requirement: SecurityRequirement<'a>) -> Self {
+        self.skill
+            .security
+            .get_or_insert_with(Vec::new)
+            .push(requirement);
+
+        self
+    }
+
+    pub fn build(self) -> Result<AgentSkill<'a>, MissingFieldError> {
+        let skill = self.skill;
+
+        MissingFieldError::check(
+            "AgentSkill",
+            [
+                ("id", !skill.id.is_empty()),
+                ("name", !skill.name.is_empty()),
+                ("description", !skill.description.is_empty()),
+            ],
+        )?;
+
+        Ok(skill)
+    }
 }
+
+impl_into_owned!(AgentSkill {
+    id,
+    name,
+    description,
+    tags,
+    examples,
+    input_modes,
+    output_modes,
+    security,
+});
@@ -1,7 +1,6 @@
+use std::borrow::Cow;
 
-use serde::{
-    Deserialize, Deserializer, Serializer,
-};
+use serde::{Deserialize, Deserializer, Serializer};
 
 /// Provides a declaration of a combination of the target URL and the supported transport to interact with the agent.
 /// This enables agents to expose the same functionality through multiple transport protocols.
@@ -18,6 +17,8 @@ pub enum TransportProtocol {
     Grpc,
     /// "HTTP+JSON" REST-style HTTP with JSON
     HttpJson,
+    /// A transport this crate does not know, kept as written.
+    Other(String),
 }
 
 impl TransportProtocol {
@@ -26,6 +27,7 @@ impl TransportProtocol {
             Self::JsonRpc => "JSONRPC",
             Self::Grpc => "GRPC",
             Self::HttpJson => "HTTP+JSON",
+            Self::Other(transport) => transport,
         }
     }
 }
@@ -36,7 +38,7 @@ impl From<&str> for TransportProtocol {
             val if val == TransportProtocol::Grpc.as_str() => TransportProtocol::Grpc,
             val if val == TransportProtocol::HttpJson.as_str() => TransportProtocol::HttpJson,
             val if val == TransportProtocol::JsonRpc.as_str() => TransportProtocol::JsonRpc,
-            _ => TransportProtocol::JsonRpc,
+            val => TransportProtocol::Other(val.to_owned()),
         }
     }
 }
@@ -55,5 +57,5 @@ pub fn deserialize_transport<'de, D>(deserializer: D) -> Result<TransportProtoco
 where
     D: Deserializer<'de>,
 {
-    Ok(<&'de str>::deserialize(deserializer)?.into())
+    Ok(Cow::<'de, str>::deserialize(deserializer)?.as_ref().into())
 }
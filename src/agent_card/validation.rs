@@ -0,0 +1,334 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use crate::{AgentCard, MediaType, SecurityRequirement, SecurityScheme, TransportProtocol};
+
+/// The versions of the A2A protocol an [AgentCard] can declare in `protocolVersion`.
+pub const KNOWN_PROTOCOL_VERSIONS: &[&str] = &[
+    "0.1.0", "0.2.0", "0.2.1", "0.2.2", "0.2.3", "0.2.4", "0.2.5", "0.2.6", "0.3.0",
+];
+
+/// Checks the rules of the specification that deserializing an [AgentCard] does not enforce.
+///
+/// Deserializing keeps the input and output modes that are not valid media types,
+/// the validator reports them along with the empty lists of modes.
+///
+/// Outside of the dev mode every URL has to use `https`. The dev mode accepts `http`,
+/// for agents served from `localhost` or a test network.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct CardValidator {
+    dev_mode: bool,
+}
+
+impl CardValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept `http` URLs.
+    pub fn with_dev_mode(mut self, dev_mode: bool) -> Self {
+        self.dev_mode = dev_mode;
+
+        self
+    }
+
+    pub fn dev_mode(&self) -> bool {
+        self.dev_mode
+    }
+
+    /// All the problems of `card`, in the order of its fields. The card is valid when no diagnostic
+    /// is an error, see [CardDiagnostic::is_error].
+    pub fn validate(&self, card: &AgentCard<'_>) -> Vec<CardDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        if !KNOWN_PROTOCOL_VERSIONS.contains(&card.protocol_version()) {
+            diagnostics.push(CardDiagnostic {
+                path: "/protocolVersion".into(),
+                issue: CardIssue::UnknownProtocolVersion(card.protocol_version().to_owned()),
+            });
+        }
+
+        if let Some(issue) = self.check_url(card.url()) {
+            diagnostics.push(CardDiagnostic {
+                path: "/url".into(),
+                issue,
+            });
+        }
+
+        if let Some(interfaces) = card.additional_interfaces() {
+            for (index, interface) in interfaces.iter().enumerate() {
+                if let Some(issue) = self.check_url(&interface.url) {
+                    diagnostics.push(CardDiagnostic {
+                        path: format!("/additionalInterfaces/{index}/url"),
+                        issue,
+                    });
+                }
+            }
+
+            let transport = card.preferred_transport().cloned().unwrap_or_default();
+            if !interfaces
+                .iter()
+                .any(|interface| interface.url == card.url() && interface.transport == transport)
+            {
+                diagnostics.push(CardDiagnostic {
+                    path: "/additionalInterfaces".into(),
+                    issue: CardIssue::PreferredInterfaceMissing {
+                        url: card.url().to_owned(),
+                        transport,
+                    },
+                });
+            }
+        }
+
+        let schemes = card.security_schemes();
+        if let Some(security) = card.security() {
+            check_security("/security", security, schemes, &mut diagnostics);
+        }
+
+        for (path, modes) in [
+            ("/defaultInputModes", Some(card.default_input_modes())),
+            ("/defaultOutputModes", Some(card.default_output_modes())),
+        ] {
+            check_modes(path.into(), modes, &mut diagnostics);
+        }
+
+        let mut skill_ids = BTreeSet::new();
+
+        for (index, skill) in card.skills().iter().enumerate() {
+            let path = format!("/skills/{index}");
+
+            if !skill_ids.insert(skill.id()) {
+                diagnostics.push(CardDiagnostic {
+                    path: format!("{path}/id"),
+                    issue: CardIssue::DuplicateSkillId(skill.id().to_owned()),
+                });
+            }
+
+            if let Some(security) = skill.security() {
+                check_security(
+                    &format!("{path}/security"),
+                    security,
+                    schemes,
+                    &mut diagnostics,
+                );
+            }
+
+            check_modes(
+                format!("{path}/inputModes"),
+                skill.input_modes(),
+                &mut diagnostics,
+            );
+            check_modes(
+                format!("{path}/outputModes"),
+                skill.output_modes(),
+                &mut diagnostics,
+            );
+        }
+
+        diagnostics
+    }
+
+    /// An absolute `https` URL, or `http` in dev mode.
+    fn check_url(&self, url: &str) -> Option<CardIssue> {
+        let invalid = |reason| {
+            Some(CardIssue::InvalidUrl {
+                url: url.to_owned(),
+                reason,
+            })
+        };
+
+        let Some((scheme, rest)) = url.split_once("://") else {
+            return invalid("the URL must be absolute");
+        };
+        if !scheme.starts_with(|char: char| char.is_ascii_alphabetic())
+            || !scheme
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || "+-.".contains(char))
+        {
+            return invalid("the scheme is not valid");
+        }
+        if url.contains(char::is_whitespace) {
+            return invalid("a URL cannot contain whitespace");
+        }
+
+        let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+        let host = authority.rsplit('@').next().unwrap_or_default();
+        if host.is_empty() || host.starts_with(':') {
+            return invalid("the host is missing");
+        }
+
+        if scheme.eq_ignore_ascii_case("https") {
+            None
+        } else if scheme.eq_ignore_ascii_case("http") {
+            (!self.dev_mode).then(|| CardIssue::InsecureUrl(url.to_owned()))
+        } else {
+            invalid("the scheme must be `https`")
+        }
+    }
+}
+
+impl<'a> AgentCard<'a> {
+    /// Validate the card with the production rules, see [CardValidator].
+    pub fn validate(&self) -> Vec<CardDiagnostic> {
+        CardValidator::new().validate(self)
+    }
+}
+
+/// A problem found by [CardValidator], located by the JSON pointer of the field in the card.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CardDiagnostic {
+    /// The JSON pointer (RFC 6901) of the field, e.g. `/skills/2/id`.
+    pub path: String,
+    pub issue: CardIssue,
+}
+
+impl CardDiagnostic {
+    pub fn severity(&self) -> Severity {
+        self.issue.severity()
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity() == Severity::Error
+    }
+}
+
+impl fmt::Display for CardDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at `{}`: {}", self.severity(), self.path, self.issue)
+    }
+}
+
+/// Whether a [CardDiagnostic] breaks a MUST of the specification or a SHOULD.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => f.write_str("warning"),
+            Self::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// The problems [CardValidator] reports.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub enum CardIssue {
+    /// The URL is not absolute or cannot be used by a client.
+    InvalidUrl { url: String, reason: &'static str },
+    /// An `http` URL outside of the dev mode.
+    InsecureUrl(String),
+    /// `additionalInterfaces` has no entry for the main `url` and `preferredTransport`.
+    PreferredInterfaceMissing {
+        url: String,
+        transport: TransportProtocol,
+    },
+    /// Two skills have the same id.
+    DuplicateSkillId(String),
+    /// A security requirement names a scheme that is not declared in `securitySchemes`.
+    UnknownSecurityScheme(String),
+    /// A list of input or output modes is empty.
+    NoModes,
+    /// An input or output mode is not a valid media type, see [MediaType::validate].
+    InvalidMediaType { value: String, reason: &'static str },
+    /// `protocolVersion` is not one of [KNOWN_PROTOCOL_VERSIONS].
+    UnknownProtocolVersion(String),
+}
+
+impl CardIssue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::PreferredInterfaceMissing { .. } | Self::UnknownProtocolVersion(_) => {
+                Severity::Warning
+            }
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for CardIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUrl { url, reason } => write!(f, "`{url}` is not a valid URL, {reason}"),
+            Self::InsecureUrl(url) => write!(f, "`{url}` must use https"),
+            Self::PreferredInterfaceMissing { url, transport } => write!(
+                f,
+                "no additional interface declares `{url}` with the preferred transport {}",
+                transport.as_str()
+            ),
+            Self::DuplicateSkillId(id) => write!(f, "the skill id `{id}` is used more than once"),
+            Self::UnknownSecurityScheme(scheme) => {
+                write!(f, "the security scheme `{scheme}` is not declared")
+            }
+            Self::NoModes => f.write_str("at least one media type is required"),
+            Self::InvalidMediaType { value, reason } => {
+                write!(f, "`{value}` is not a valid media type, {reason}")
+            }
+            Self::UnknownProtocolVersion(version) => {
+                write!(f, "`{version}` is not a known protocol version")
+            }
+        }
+    }
+}
+
+fn check_modes(
+    path: String,
+    modes: Option<&[MediaType<'_>]>,
+    diagnostics: &mut Vec<CardDiagnostic>,
+) {
+    let Some(modes) = modes else {
+        return;
+    };
+
+    if modes.is_empty() {
+        diagnostics.push(CardDiagnostic {
+            path,
+            issue: CardIssue::NoModes,
+        });
+        return;
+    }
+
+    for (index, mode) in modes.iter().enumerate() {
+        if let Err(error) = mode.validate() {
+            diagnostics.push(CardDiagnostic {
+                path: format!("{path}/{index}"),
+                issue: CardIssue::InvalidMediaType {
+                    value: error.media_type,
+                    reason: error.reason,
+                },
+            });
+        }
+    }
+}
+
+/// Report the schemes named in `requirements` that are not in `schemes`.
+fn check_security(
+    path: &str,
+    requirements: &[SecurityRequirement<'_>],
+    schemes: Option<&BTreeMap<Cow<'_, str>, SecurityScheme<'_>>>,
+    diagnostics: &mut Vec<CardDiagnostic>,
+) {
+    for (index, requirement) in requirements.iter().enumerate() {
+        for scheme in requirement
+            .keys()
+            .filter(|scheme| !schemes.is_some_and(|schemes| schemes.contains_key(*scheme)))
+        {
+            diagnostics.push(CardDiagnostic {
+                path: format!("{path}/{index}/{}", escape(scheme)),
+                issue: CardIssue::UnknownSecurityScheme(scheme.to_string()),
+            });
+        }
+    }
+}
+
+/// Escape a member name for a JSON pointer.
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,144 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Serialize `value` with the JSON Canonicalization Scheme (RFC 8785), the deterministic form
+/// used to sign an [crate::AgentCard] or to compare and hash messages.
+///
+/// - object members are sorted by the UTF-16 code units of their names
+/// - numbers are written like ECMAScript's `Number.prototype.toString`. Every number is an
+///   IEEE 754 double, so integers beyond 2^53 lose precision as the RFC requires
+/// - strings only escape `"`, `\` and the control characters
+/// - there is no whitespace
+///
+/// serde_json writes non-finite floats as `null`, so do these functions.
+pub fn to_canonical_string<T>(value: &T) -> Result<String, serde_json::Error>
+where
+    T: Serialize + ?Sized,
+{
+    let mut canonical = String::new();
+    write_value(&serde_json::to_value(value)?, &mut canonical);
+
+    Ok(canonical)
+}
+
+/// The UTF-8 bytes of [to_canonical_string].
+pub fn to_canonical_vec<T>(value: &T) -> Result<Vec<u8>, serde_json::Error>
+where
+    T: Serialize + ?Sized,
+{
+    to_canonical_string(value).map(String::into_bytes)
+}
+
+fn write_value(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(true) => out.push_str("true"),
+        Value::Bool(false) => out.push_str("false"),
+        Value::Number(number) => match number.as_f64() {
+            Some(number) => write_number(number, out),
+            None => out.push_str("null"),
+        },
+        Value::String(string) => write_string(string, out),
+        Value::Array(values) => {
+            out.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_value(value, out);
+            }
+            out.push(']');
+        }
+        Value::Object(members) => {
+            let mut members = members.iter().collect::<Vec<_>>();
+            members.sort_by(|(left, _), (right, _)| left.encode_utf16().cmp(right.encode_utf16()));
+
+            out.push('{');
+            for (index, (name, value)) in members.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_string(name, out);
+                out.push(':');
+                write_value(value, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(string: &str, out: &mut String) {
+    out.push('"');
+
+    for char in string.chars() {
+        match char {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            char if char < ' ' => {
+                let _ = write!(out, "\\u{:04x}", char as u32);
+            }
+            char => out.push(char),
+        }
+    }
+
+    out.push('"');
+}
+
+/// ECMAScript `Number::toString` (ECMA-262 section 6.1.6.1.20) of a finite number.
+fn write_number(number: f64, out: &mut String) {
+    if number == 0.0 {
+        // Also -0.
+        out.push('0');
+        return;
+    }
+    if number < 0.0 {
+        out.push('-');
+    }
+
+    // The shortest digits that round trip, e.g. `1.2345e-7`. When two candidates are as close to
+    // the number Rust picks the larger one and ECMAScript the even one, so the number is formatted
+    // again to as many digits with exact rounding, which rounds ties to even.
+    let shortest = format!("{:e}", number.abs());
+    let precision = shortest
+        .split_once('e')
+        .map_or(0, |(mantissa, _)| mantissa.len().saturating_sub(2));
+    let scientific = format!("{:.*e}", precision, number.abs());
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .unwrap_or((scientific.as_str(), "0"));
+    let digits = mantissa.replace('.', "");
+    let digits = digits.as_str();
+
+    // The value is 0.digits × 10^n.
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().unwrap_or_default() + 1;
+
+    if k <= n && n <= 21 {
+        out.push_str(digits);
+        out.extend(std::iter::repeat_n('0', (n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        let (integer, fraction) = digits.split_at(n as usize);
+        out.push_str(integer);
+        out.push('.');
+        out.push_str(fraction);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', -n as usize));
+        out.push_str(digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        out.push_str(first);
+        if !rest.is_empty() {
+            out.push('.');
+            out.push_str(rest);
+        }
+        let _ = write!(out, "e{}{}", if n > 0 { '+' } else { '-' }, (n - 1).abs());
+    }
+}
@@ -1,7 +1,19 @@
+use std::{borrow::Cow, fmt, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{impl_into_owned, AgentInterface, JsonValue, TaskState};
+
+/// The error codes of JSON-RPC 2.0 and of the A2A protocol.
+///
 /// -32000 to -32099 error codes are reserved for implementation-defined server-errors.
 /// A2A-specific errors use this range.
-#[derive(Debug, PartialEq, Eq)]
-pub enum JsonRpcError {
+///
+/// Converting to and from an `i64` is lossless: codes without a variant of their own
+/// are kept in [JsonRpcErrorCode::ServerError] or [JsonRpcErrorCode::Unknown].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Serialize, Deserialize)]
+#[serde(from = "i64", into = "i64")]
+pub enum JsonRpcErrorCode {
     ParseError,
     InvalidRequest,
     MethodNotFound,
@@ -14,10 +26,31 @@ pub enum JsonRpcError {
     ContentTypeNotSupportedError,
     InvalidAgentResponseError,
     AuthenticatedExtendedCardNotConfiguredError,
-    UnknownErrorEncountered,
+    /// An implementation-defined server error in the -32000 to -32099 range that A2A does not define.
+    ServerError(i64),
+    /// An error code outside of the codes defined by JSON-RPC 2.0 and A2A.
+    Unknown(i64),
 }
 
-impl JsonRpcError {
+impl JsonRpcErrorCode {
+    /// The range of codes reserved for implementation-defined server errors.
+    pub const SERVER_ERROR_RANGE: core::ops::RangeInclusive<i64> = -32099..=-32000;
+
+    const DEFINED: [Self; 12] = [
+        Self::ParseError,
+        Self::InvalidRequest,
+        Self::MethodNotFound,
+        Self::InvalidParams,
+        Self::InternalError,
+        Self::TaskNotFoundError,
+        Self::TaskNotCancelableError,
+        Self::PushNotificationNotSupportedError,
+        Self::UnsupportedOperationError,
+        Self::ContentTypeNotSupportedError,
+        Self::InvalidAgentResponseError,
+        Self::AuthenticatedExtendedCardNotConfiguredError,
+    ];
+
     pub fn error_code(&self) -> i64 {
         match self {
             Self::ParseError => -32700,
@@ -32,15 +65,11 @@ impl JsonRpcError {
             Self::ContentTypeNotSupportedError => -32005,
             Self::InvalidAgentResponseError => -32006,
             Self::AuthenticatedExtendedCardNotConfiguredError => -32007,
-            Self::UnknownErrorEncountered => -32000,
+            Self::ServerError(code) | Self::Unknown(code) => *code,
         }
     }
 
-    pub fn foo(value: i64) -> Self {
-        (value).into()
-    }
-
-    pub fn description(&self) -> &str {
+    pub fn description(&self) -> &'static str {
         match self {
             Self::ParseError => "Server received JSON that was not well-formed.",
             Self::InvalidRequest => {
@@ -56,37 +85,550 @@ impl JsonRpcError {
             Self::ContentTypeNotSupportedError => "A Media Type provided in the request's message.parts (or implied for an artifact) is not supported by the agent or the specific skill being invoked.",
             Self::InvalidAgentResponseError =>  "Agent generated an invalid response for the requested method",
             Self::AuthenticatedExtendedCardNotConfiguredError => "The agent does not have an Authenticated Extended Card configured.",
-            Self::UnknownErrorEncountered => "An unknown error was parsed. If this error is valid then open an issue on the repository"
+            Self::ServerError(_) => "An implementation-defined server error was encountered.",
+            Self::Unknown(_) => "An unknown error was parsed. If this error is valid then open an issue on the repository"
         }
     }
 }
 
-impl From<i64> for JsonRpcError {
+impl From<i64> for JsonRpcErrorCode {
     fn from(value: i64) -> Self {
-        match value {
-            val if val == Self::ParseError.error_code() => Self::ParseError,
-            val if val == Self::InvalidRequest.error_code() => Self::InvalidRequest,
-            val if val == Self::MethodNotFound.error_code() => Self::MethodNotFound,
-            val if val == Self::InvalidParams.error_code() => Self::InvalidParams,
-            val if val == Self::InternalError.error_code() => Self::InternalError,
-            val if val == Self::TaskNotFoundError.error_code() => Self::TaskNotFoundError,
-            val if val == Self::TaskNotCancelableError.error_code() => Self::TaskNotCancelableError,
-            val if val == Self::PushNotificationNotSupportedError.error_code() => {
-                Self::PushNotificationNotSupportedError
+        match Self::DEFINED
+            .into_iter()
+            .find(|code| code.error_code() == value)
+        {
+            Some(code) => code,
+            None if Self::SERVER_ERROR_RANGE.contains(&value) => Self::ServerError(value),
+            None => Self::Unknown(value),
+        }
+    }
+}
+
+impl From<JsonRpcErrorCode> for i64 {
+    fn from(value: JsonRpcErrorCode) -> Self {
+        value.error_code()
+    }
+}
+
+impl fmt::Display for JsonRpcErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.error_code(), self.description())
+    }
+}
+
+/// Represents a JSON-RPC 2.0 Error object, included in an error response.
+///
+/// Building one from a [JsonRpcErrorCode] fills in the standard message of the code.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct JsonRpcError<'a> {
+    /// A number that indicates the error type that occurred.
+    pub code: JsonRpcErrorCode,
+    /// A string providing a short description of the error.
+    #[serde(borrow)]
+    pub message: Cow<'a, str>,
+    /// A primitive or structured value containing additional information about the error.
+    #[serde(borrow)]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<JsonValue<'a>>,
+}
+
+impl<'a> JsonRpcError<'a> {
+    /// An error with the standard message of `code`.
+    pub fn new(code: JsonRpcErrorCode) -> Self {
+        Self {
+            code,
+            message: code.description().into(),
+            data: None,
+        }
+    }
+
+    /// Replace the standard message.
+    pub fn with_message(mut self, message: impl Into<Cow<'a, str>>) -> Self {
+        self.message = message.into();
+
+        self
+    }
+
+    /// Attach additional information about the error.
+    pub fn with_data(mut self, data: impl Into<JsonValue<'a>>) -> Self {
+        self.data = Some(data.into());
+
+        self
+    }
+
+    /// `TaskNotFoundError` with the id of the missing task as `{"taskId": ...}` in `data`.
+    pub fn task_not_found(task_id: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(JsonRpcErrorCode::TaskNotFoundError).with_data(Self::task_data(task_id))
+    }
+
+    /// `TaskNotCancelableError` with the id of the task as `{"taskId": ...}` in `data`.
+    pub fn task_not_cancelable(task_id: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(JsonRpcErrorCode::TaskNotCancelableError).with_data(Self::task_data(task_id))
+    }
+
+    /// `ContentTypeNotSupportedError` with the rejected media type as `{"mimeType": ...}` in `data`.
+    pub fn content_type_not_supported(mime_type: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(JsonRpcErrorCode::ContentTypeNotSupportedError).with_data(JsonValue::from_iter([
+            (
+                Cow::Borrowed("mimeType"),
+                JsonValue::String(mime_type.into()),
+            ),
+        ]))
+    }
+
+    fn task_data(task_id: impl Into<Cow<'a, str>>) -> JsonValue<'a> {
+        JsonValue::from_iter([(Cow::Borrowed("taskId"), JsonValue::String(task_id.into()))])
+    }
+}
+
+impl<'a> From<JsonRpcErrorCode> for JsonRpcError<'a> {
+    fn from(value: JsonRpcErrorCode) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<'a> From<JsonRpcError<'a>> for JsonRpcErrorCode {
+    fn from(value: JsonRpcError<'a>) -> Self {
+        value.code
+    }
+}
+
+impl<'a> fmt::Display for JsonRpcError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code.error_code(), self.message)
+    }
+}
+
+impl<'a> std::error::Error for JsonRpcError<'a> {}
+
+impl_into_owned!(JsonRpcError {
+    code,
+    message,
+    data,
+});
+
+/// Returned by a builder when a required field was never set.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct MissingFieldError {
+    /// The type that was being built, e.g. `AgentCard`.
+    pub type_name: &'static str,
+    /// The name of the missing field as it appears in JSON, e.g. `defaultInputModes`.
+    pub field: &'static str,
+}
+
+impl MissingFieldError {
+    /// Fails with the first field that is not present.
+    pub(crate) fn check<const N: usize>(
+        type_name: &'static str,
+        fields: [(&'static str, bool); N],
+    ) -> Result<(), Self> {
+        match fields.into_iter().find(|(_, present)| !present) {
+            Some((field, _)) => Err(Self { type_name, field }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for MissingFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is missing the required field `{}`",
+            self.type_name, self.field
+        )
+    }
+}
+
+impl std::error::Error for MissingFieldError {}
+
+/// Returned when a string is not a valid media type, see [crate::MediaType::parse].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct MediaTypeError {
+    /// The text that failed to parse.
+    pub media_type: String,
+    /// The rule of RFC 9110 that was broken.
+    pub reason: &'static str,
+}
+
+impl fmt::Display for MediaTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid media type, {}",
+            self.media_type, self.reason
+        )
+    }
+}
+
+impl std::error::Error for MediaTypeError {}
+
+/// Returned when the base64 content of a file cannot be decoded, see [crate::FileDecoder].
+#[derive(Debug)]
+pub enum FileBytesError {
+    /// The content is not valid base64. `offset` is the position of the first invalid character.
+    InvalidBase64 { offset: usize },
+    /// The decoded content would be larger than the limit of the decoder.
+    TooLarge { size: usize, max_size: usize },
+    /// Writing the decoded content failed.
+    Io(io::Error),
+}
+
+impl From<io::Error> for FileBytesError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl fmt::Display for FileBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBase64 { offset } => {
+                write!(f, "The file content is not valid base64 at offset {offset}")
             }
-            val if val == Self::UnsupportedOperationError.error_code() => {
-                Self::UnsupportedOperationError
+            Self::TooLarge { size, max_size } => write!(
+                f,
+                "The file content of {size} bytes is larger than the limit of {max_size} bytes"
+            ),
+            Self::Io(error) => write!(f, "Writing the file content failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for FileBytesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Returned when the content of a file referenced by a uri cannot be fetched, see [crate::FileFetcher].
+#[derive(Debug)]
+pub enum FileFetchError {
+    /// The fetcher does not handle the scheme of the uri.
+    UnsupportedScheme(String),
+    /// The uri is malformed.
+    InvalidUri { uri: String, reason: &'static str },
+    /// The uri points outside of the files the fetcher may serve.
+    NotAllowed(String),
+    /// The content is invalid or too large.
+    Bytes(FileBytesError),
+    /// Reading the content failed.
+    Io(io::Error),
+}
+
+impl From<FileBytesError> for FileFetchError {
+    fn from(value: FileBytesError) -> Self {
+        Self::Bytes(value)
+    }
+}
+
+impl From<io::Error> for FileFetchError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl fmt::Display for FileFetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedScheme(uri) => {
+                write!(f, "Cannot fetch `{uri}`, its scheme is not supported")
             }
-            val if val == Self::ContentTypeNotSupportedError.error_code() => {
-                Self::ContentTypeNotSupportedError
+            Self::InvalidUri { uri, reason } => write!(f, "`{uri}` is not a valid URI, {reason}"),
+            Self::NotAllowed(uri) => write!(f, "Fetching `{uri}` is not allowed"),
+            Self::Bytes(error) => error.fmt(f),
+            Self::Io(error) => write!(f, "Reading the file failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for FileFetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Bytes(error) => Some(error),
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// A uri the fetcher cannot serve is an `InvalidParams` error, a failed read an `InternalError`.
+impl<'a> From<FileFetchError> for JsonRpcError<'a> {
+    fn from(value: FileFetchError) -> Self {
+        match value {
+            FileFetchError::Bytes(error) => error.into(),
+            FileFetchError::Io(_) => {
+                JsonRpcError::new(JsonRpcErrorCode::InternalError).with_message(value.to_string())
             }
-            val if val == Self::InvalidAgentResponseError.error_code() => {
-                Self::InvalidAgentResponseError
+            _ => JsonRpcError::new(JsonRpcErrorCode::InvalidParams).with_message(value.to_string()),
+        }
+    }
+}
+
+/// Invalid and oversized content is an `InvalidParams` error, a failed write an `InternalError`.
+impl<'a> From<FileBytesError> for JsonRpcError<'a> {
+    fn from(value: FileBytesError) -> Self {
+        let code = match value {
+            FileBytesError::Io(_) => JsonRpcErrorCode::InternalError,
+            _ => JsonRpcErrorCode::InvalidParams,
+        };
+
+        JsonRpcError::new(code).with_message(value.to_string())
+    }
+}
+
+/// Returned when an [crate::AgentCard] cannot be signed, or when none of its signatures is valid.
+#[cfg(feature = "jws")]
+#[derive(Debug)]
+pub enum CardSignatureError {
+    /// The card has no signatures.
+    Unsigned,
+    /// The `alg` of a signature is not one of [crate::JwsAlgorithm].
+    UnsupportedAlgorithm(String),
+    /// The header lists critical extensions, none of which is supported.
+    UnsupportedCritical(Vec<String>),
+    /// A private key cannot be loaded, or a public key does not fit the algorithm.
+    InvalidKey(&'static str),
+    /// The protected header or the signature is not valid base64url.
+    Malformed(&'static str),
+    /// No key was found for a signature with this `kid`.
+    KeyNotFound(Option<String>),
+    /// The `jku` of a signature does not start with a prefix trusted by the [crate::JkuResolver].
+    UntrustedJwkSetUrl(String),
+    /// The key set at the `jku` cannot be fetched.
+    Fetch(FileFetchError),
+    /// The card, a header or a key set cannot be encoded or decoded.
+    Json(serde_json::Error),
+    /// The private key failed to sign.
+    SigningFailed,
+    /// The signature does not match the card.
+    Mismatch,
+}
+
+#[cfg(feature = "jws")]
+impl From<FileFetchError> for CardSignatureError {
+    fn from(value: FileFetchError) -> Self {
+        Self::Fetch(value)
+    }
+}
+
+#[cfg(feature = "jws")]
+impl From<serde_json::Error> for CardSignatureError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+#[cfg(feature = "jws")]
+impl fmt::Display for CardSignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsigned => f.write_str("The agent card is not signed"),
+            Self::UnsupportedAlgorithm(alg) => {
+                write!(f, "The signature algorithm `{alg}` is not supported")
             }
-            val if val == Self::AuthenticatedExtendedCardNotConfiguredError.error_code() => {
-                Self::AuthenticatedExtendedCardNotConfiguredError
+            Self::UnsupportedCritical(crit) => write!(
+                f,
+                "The critical header parameters `{}` are not supported",
+                crit.join("`, `")
+            ),
+            Self::InvalidKey(reason) => write!(f, "Invalid key, {reason}"),
+            Self::Malformed(reason) => write!(f, "Malformed signature, {reason}"),
+            Self::KeyNotFound(Some(kid)) => write!(f, "No key found with the id `{kid}`"),
+            Self::KeyNotFound(None) => f.write_str("No key found for the signature"),
+            Self::UntrustedJwkSetUrl(jku) => write!(f, "The key set URL `{jku}` is not trusted"),
+            Self::Fetch(error) => write!(f, "Fetching the key set failed: {error}"),
+            Self::Json(error) => error.fmt(f),
+            Self::SigningFailed => f.write_str("Signing the agent card failed"),
+            Self::Mismatch => f.write_str("The signature does not match the agent card"),
+        }
+    }
+}
+
+#[cfg(feature = "jws")]
+impl std::error::Error for CardSignatureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Fetch(error) => Some(error),
+            Self::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Returned when [crate::CardDiscovery] cannot find the agent card of a domain.
+#[derive(Debug)]
+pub enum DiscoveryError {
+    /// The base is neither a domain nor a URL.
+    InvalidUrl(String),
+    /// No card is served at the well-known paths of this origin.
+    NotFound(String),
+    /// The server answered with an unexpected status.
+    Status { url: String, status: u16 },
+    /// The card supports an authenticated extended card but declares no JSON-RPC interface to request it.
+    NoJsonRpcInterface,
+    /// The JSON-RPC interface of the card is not on the origin the card was found at,
+    /// so the authorization is not sent to it.
+    UntrustedExtendedCardUrl(String),
+    /// The agent answered `agent/getAuthenticatedExtendedCard` with an error.
+    JsonRpc(JsonRpcError<'static>),
+    /// The request failed.
+    Http(io::Error),
+    /// The body is not an agent card or a JSON-RPC response.
+    Json(serde_json::Error),
+    /// No signature of the card is valid.
+    #[cfg(feature = "jws")]
+    Signature(CardSignatureError),
+}
+
+impl From<io::Error> for DiscoveryError {
+    fn from(value: io::Error) -> Self {
+        Self::Http(value)
+    }
+}
+
+impl From<serde_json::Error> for DiscoveryError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+#[cfg(feature = "jws")]
+impl From<CardSignatureError> for DiscoveryError {
+    fn from(value: CardSignatureError) -> Self {
+        Self::Signature(value)
+    }
+}
+
+impl fmt::Display for DiscoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUrl(base) => write!(f, "`{base}` is not a domain or a URL"),
+            Self::NotFound(origin) => write!(f, "No agent card found at `{origin}`"),
+            Self::Status { url, status } => write!(f, "`{url}` answered with status {status}"),
+            Self::NoJsonRpcInterface => f.write_str(
+                "The agent card has no JSON-RPC interface to request the extended card from",
+            ),
+            Self::UntrustedExtendedCardUrl(url) => write!(
+                f,
+                "The extended agent card is not requested from `{url}`, outside of the origin of the card"
+            ),
+            Self::JsonRpc(error) => {
+                write!(f, "Requesting the extended agent card failed: {error}")
             }
-            _ => Self::UnknownErrorEncountered,
+            Self::Http(error) => write!(f, "The request failed: {error}"),
+            Self::Json(error) => write!(f, "Invalid agent card: {error}"),
+            #[cfg(feature = "jws")]
+            Self::Signature(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for DiscoveryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::JsonRpc(error) => Some(error),
+            Self::Http(error) => Some(error),
+            Self::Json(error) => Some(error),
+            #[cfg(feature = "jws")]
+            Self::Signature(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Returned by [crate::connect_with_fallback] when no interface of the agent could be connected to.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct ConnectError<E> {
+    /// Every interface tried with its error, in order. Empty when the client supports
+    /// none of the transports of the agent.
+    pub failures: Vec<(AgentInterface<'static>, E)>,
+    /// The last failure was a [crate::Fallback::Stop], the interfaces after it were not tried.
+    pub stopped: bool,
+}
+
+impl<E> ConnectError<E> {
+    /// No interface was tried.
+    pub fn is_unsupported(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The error of the last interface tried.
+    pub fn last_error(&self) -> Option<&E> {
+        self.failures.last().map(|(_, error)| error)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ConnectError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.failures.last() {
+            Some((interface, error)) => write!(
+                f,
+                "Connecting to the agent failed on {} interface(s){}, the last at `{}` over {}: {error}",
+                self.failures.len(),
+                match self.stopped {
+                    true => " before it was stopped",
+                    false => "",
+                },
+                interface.url,
+                interface.transport.as_str()
+            ),
+            None => f.write_str("The agent has no interface with a supported transport"),
         }
     }
 }
+
+impl<E: std::error::Error + 'static> std::error::Error for ConnectError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.last_error().map(|error| error as _)
+    }
+}
+
+/// A status that cannot follow the current state of a task, see [TaskState::can_transition_to].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct TaskTransitionError<'a> {
+    pub task_id: Cow<'a, str>,
+    pub from: TaskState,
+    pub to: TaskState,
+}
+
+impl<'a> TaskTransitionError<'a> {
+    /// The task was asked to be canceled after it reached a terminal state.
+    pub fn is_not_cancelable(&self) -> bool {
+        self.to == TaskState::Canceled
+    }
+}
+
+impl<'a> fmt::Display for TaskTransitionError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Task `{}` cannot move from `{}` to `{}`",
+            self.task_id, self.from, self.to
+        )
+    }
+}
+
+impl<'a> std::error::Error for TaskTransitionError<'a> {}
+
+/// A task that cannot be canceled becomes a `TaskNotCancelableError`,
+/// any other illegal transition an `UnsupportedOperationError`.
+/// Both carry the id of the task as `{"taskId": ...}` in `data`.
+impl<'a> From<TaskTransitionError<'a>> for JsonRpcError<'a> {
+    fn from(value: TaskTransitionError<'a>) -> Self {
+        let message = value.to_string();
+
+        if value.is_not_cancelable() {
+            JsonRpcError::task_not_cancelable(value.task_id).with_message(message)
+        } else {
+            JsonRpcError::new(JsonRpcErrorCode::UnsupportedOperationError)
+                .with_message(message)
+                .with_data(JsonRpcError::task_data(value.task_id))
+        }
+    }
+}
+
+impl_into_owned!(TaskTransitionError { task_id, from, to });
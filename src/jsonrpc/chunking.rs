@@ -0,0 +1,362 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    ops::Range,
+};
+
+use crate::{
+    jsonrpc::TaskArtifactUpdateEvent, Artifact, FilePart, FileWith, JsonStrMemKV, JsonValue,
+    MissingFieldError, Part, TextPart,
+};
+
+/// The member of the event `metadata` set by [ArtifactChunker] when the first part of a chunk
+/// continues the last part of the previous chunk, because a long text or file part was split.
+///
+/// This is not part of the A2A protocol, so it is named after this crate to not clash with
+/// the metadata of other extensions.
+pub const ARTIFACT_CHUNK_CONTINUES_PART: &str = "urn:agentic-types:artifact-chunking:continuesPart";
+
+/// Splits an [Artifact] into `artifact-update` events whose parts hold at most `max_chunk_size` bytes.
+///
+/// Text parts are split on character boundaries so that every piece is valid on its own.
+/// Other parts are never split and count with the length of their JSON encoding.
+///
+/// A split part is only joined again by a consumer that knows [ARTIFACT_CHUNK_CONTINUES_PART],
+/// such as [ArtifactAssembler] or [crate::jsonrpc::TaskReducer]. Any other consumer sees the pieces
+/// as separate parts, which still reads as the same text but makes several files of one.
+/// Base64 file parts are thus only split, on multiples of 4 characters, when
+/// [ArtifactChunker::with_split_files] is set.
+///
+/// The first event carries the name, description, metadata and extensions of the artifact,
+/// the following ones only its id and `append` set. The last event has `lastChunk` set.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ArtifactChunker {
+    max_chunk_size: usize,
+    split_files: bool,
+}
+
+impl ArtifactChunker {
+    /// A `max_chunk_size` of 0 is treated as 1.
+    pub fn new(max_chunk_size: usize) -> Self {
+        Self {
+            max_chunk_size: max_chunk_size.max(1),
+            split_files: false,
+        }
+    }
+
+    /// Also split base64 file parts, for consumers that join them again.
+    pub fn with_split_files(mut self, split_files: bool) -> Self {
+        self.split_files = split_files;
+
+        self
+    }
+
+    pub fn max_chunk_size(&self) -> usize {
+        self.max_chunk_size
+    }
+
+    pub fn split_files(&self) -> bool {
+        self.split_files
+    }
+
+    /// Fails when the ids of the task, context or artifact are empty.
+    pub fn chunk<'a>(
+        &self,
+        task_id: impl Into<Cow<'a, str>>,
+        context_id: impl Into<Cow<'a, str>>,
+        mut artifact: Artifact<'a>,
+    ) -> Result<Vec<TaskArtifactUpdateEvent<'a>>, MissingFieldError> {
+        let task_id = task_id.into();
+        let context_id = context_id.into();
+
+        let mut chunks = Vec::<(Vec<Part<'a>>, bool)>::new();
+        let mut size = 0;
+
+        for part in std::mem::take(artifact.parts_mut()) {
+            for (index, piece) in self.split(part).into_iter().enumerate() {
+                let piece_size = part_size(&piece);
+
+                match chunks.last_mut() {
+                    Some((parts, _)) if size + piece_size <= self.max_chunk_size => {
+                        parts.push(piece);
+                        size += piece_size;
+                    }
+                    _ => {
+                        chunks.push((vec![piece], index > 0));
+                        size = piece_size;
+                    }
+                }
+            }
+        }
+
+        if chunks.is_empty() {
+            chunks.push((Vec::new(), false));
+        }
+
+        let last = chunks.len() - 1;
+        let tail = artifact.without_details();
+        let mut head = Some(artifact);
+
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, (parts, continues_part))| {
+                let mut chunk = head.take().unwrap_or_else(|| tail.clone());
+                *chunk.parts_mut() = parts;
+
+                let mut event = TaskArtifactUpdateEvent::builder()
+                    .task_id(task_id.clone())
+                    .context_id(context_id.clone())
+                    .artifact(chunk)
+                    .append(index > 0)
+                    .last_chunk(index == last);
+
+                if continues_part {
+                    event = event.metadata(JsonStrMemKV::from([(
+                        Cow::Borrowed(ARTIFACT_CHUNK_CONTINUES_PART),
+                        JsonValue::Bool(true),
+                    )]));
+                }
+
+                event.build()
+            })
+            .collect()
+    }
+
+    fn split<'a>(&self, part: Part<'a>) -> Vec<Part<'a>> {
+        match &part {
+            Part::Text(text) => split_ranges(&text.text, self.max_chunk_size, 1)
+                .into_iter()
+                .map(|range| {
+                    Part::Text(TextPart {
+                        base: text.base.clone(),
+                        text: slice(&text.text, range),
+                    })
+                })
+                .collect(),
+            Part::File(FilePart {
+                base,
+                file: FileWith::Bytes(file),
+            }) if self.split_files => split_ranges(file.bytes(), self.max_chunk_size, 4)
+                .into_iter()
+                .map(|range| {
+                    let mut piece = file.clone();
+                    let bytes = piece.bytes_mut();
+                    *bytes = slice(bytes, range);
+
+                    Part::File(FilePart {
+                        base: base.clone(),
+                        file: FileWith::Bytes(piece),
+                    })
+                })
+                .collect(),
+            _ => vec![part],
+        }
+    }
+}
+
+/// Rebuilds the artifacts sent by [ArtifactChunker], or by any agent streaming `artifact-update` events.
+///
+/// An artifact is complete once an event with `lastChunk` arrives, or when its first event has
+/// neither `append` nor `lastChunk` set, which is how an artifact that is not chunked is sent.
+#[derive(Debug, Default, Clone)]
+pub struct ArtifactAssembler<'a> {
+    pending: BTreeMap<Cow<'a, str>, Artifact<'a>>,
+    completed: BTreeSet<Cow<'a, str>>,
+}
+
+impl<'a> ArtifactAssembler<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the chunk of an event and return the artifact once it is complete.
+    pub fn push(
+        &mut self,
+        event: &TaskArtifactUpdateEvent<'a>,
+    ) -> Result<Option<Artifact<'a>>, ArtifactAssemblyError<'a>> {
+        let chunk = event.artifact();
+        let artifact_id = Cow::Owned(chunk.artifact_id().to_owned());
+
+        if self.completed.contains(&artifact_id) {
+            return Err(ArtifactAssemblyError::AlreadyComplete(artifact_id));
+        }
+
+        let append = event.append() == Some(true);
+        let last_chunk = match event.last_chunk() {
+            Some(last_chunk) => last_chunk,
+            None => !append && !self.pending.contains_key(&artifact_id),
+        };
+
+        match (append, self.pending.get_mut(&artifact_id)) {
+            (false, None) => {
+                self.pending.insert(artifact_id.clone(), chunk.clone());
+            }
+            (false, Some(_)) => return Err(ArtifactAssemblyError::AlreadyStarted(artifact_id)),
+            (true, None) => return Err(ArtifactAssemblyError::NotStarted(artifact_id)),
+            (true, Some(artifact)) => {
+                let mut parts = chunk.parts().iter().cloned();
+
+                if continues_part(event) {
+                    let continued = artifact.parts_mut().last_mut().zip(parts.next());
+
+                    if !continued.is_some_and(|(last, first)| continue_part(last, first)) {
+                        return Err(ArtifactAssemblyError::InvalidContinuation(artifact_id));
+                    }
+                }
+                artifact.parts_mut().extend(parts);
+            }
+        }
+
+        if !last_chunk {
+            return Ok(None);
+        }
+
+        let artifact = self.pending.remove(&artifact_id);
+        self.completed.insert(artifact_id);
+
+        Ok(artifact)
+    }
+
+    /// The ids of the artifacts still waiting for their last chunk.
+    pub fn pending(&self) -> impl Iterator<Item = &str> {
+        self.pending.keys().map(|artifact_id| artifact_id.as_ref())
+    }
+
+    /// Ends the stream, failing if an artifact never received its last chunk.
+    pub fn finish(self) -> Result<(), ArtifactAssemblyError<'a>> {
+        if self.pending.is_empty() {
+            Ok(())
+        } else {
+            Err(ArtifactAssemblyError::Incomplete(
+                self.pending.into_keys().collect(),
+            ))
+        }
+    }
+}
+
+/// A stream of `artifact-update` events that [ArtifactAssembler] cannot rebuild an artifact from.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ArtifactAssemblyError<'a> {
+    /// An `append` chunk for an artifact whose first chunk was never received.
+    NotStarted(Cow<'a, str>),
+    /// A first chunk for an artifact that is still being received.
+    AlreadyStarted(Cow<'a, str>),
+    /// A chunk for an artifact whose last chunk was already received.
+    AlreadyComplete(Cow<'a, str>),
+    /// A chunk continues the previous part, but the two parts are not text or file bytes of the same kind.
+    InvalidContinuation(Cow<'a, str>),
+    /// The stream ended before the last chunk of these artifacts.
+    Incomplete(Vec<Cow<'a, str>>),
+}
+
+impl<'a> fmt::Display for ArtifactAssemblyError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotStarted(artifact_id) => {
+                write!(
+                    f,
+                    "Received a chunk of `{artifact_id}` before its first chunk"
+                )
+            }
+            Self::AlreadyStarted(artifact_id) => write!(
+                f,
+                "Received a first chunk of `{artifact_id}` while it is still being received"
+            ),
+            Self::AlreadyComplete(artifact_id) => {
+                write!(
+                    f,
+                    "Received a chunk of `{artifact_id}` after its last chunk"
+                )
+            }
+            Self::InvalidContinuation(artifact_id) => write!(
+                f,
+                "A chunk of `{artifact_id}` continues a part of a different kind"
+            ),
+            Self::Incomplete(artifact_ids) => write!(
+                f,
+                "The stream ended before the last chunk of `{}`",
+                artifact_ids.join("`, `")
+            ),
+        }
+    }
+}
+
+impl<'a> std::error::Error for ArtifactAssemblyError<'a> {}
+
+pub(crate) fn continues_part(event: &TaskArtifactUpdateEvent<'_>) -> bool {
+    event
+        .metadata()
+        .and_then(|metadata| metadata.get(ARTIFACT_CHUNK_CONTINUES_PART))
+        .and_then(JsonValue::as_bool)
+        .unwrap_or_default()
+}
+
+/// Append `next` to `last` if both are text or both are file bytes.
+pub(crate) fn continue_part<'a>(last: &mut Part<'a>, next: Part<'a>) -> bool {
+    match (last, next) {
+        (Part::Text(last), Part::Text(next)) => {
+            last.text.to_mut().push_str(&next.text);
+
+            true
+        }
+        (
+            Part::File(FilePart {
+                file: FileWith::Bytes(last),
+                ..
+            }),
+            Part::File(FilePart {
+                file: FileWith::Bytes(next),
+                ..
+            }),
+        ) => {
+            last.bytes_mut().to_mut().push_str(next.bytes());
+
+            true
+        }
+        _ => false,
+    }
+}
+
+fn part_size(part: &Part<'_>) -> usize {
+    match part {
+        Part::Text(text) => text.text.len(),
+        Part::File(FilePart {
+            file: FileWith::Bytes(file),
+            ..
+        }) => file.bytes().len(),
+        _ => serde_json::to_string(part).map_or(0, |json| json.len()),
+    }
+}
+
+/// Ranges of at most `max` bytes, each ending on a character boundary and, except for the last one,
+/// on a multiple of `align`. A range is never empty, so it can exceed `max` when `max` is smaller
+/// than a character or than `align`.
+fn split_ranges(value: &str, max: usize, align: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    while value.len() - start > max {
+        let mut end = (start + (max / align * align).max(align)).min(value.len());
+        while !value.is_char_boundary(end) {
+            end += 1;
+        }
+        if end == value.len() {
+            break;
+        }
+
+        ranges.push(start..end);
+        start = end;
+    }
+
+    ranges.push(start..value.len());
+    ranges
+}
+
+fn slice<'a>(value: &Cow<'a, str>, range: Range<usize>) -> Cow<'a, str> {
+    match value {
+        Cow::Borrowed(value) => Cow::Borrowed(&value[range]),
+        Cow::Owned(value) => Cow::Owned(value[range].to_owned()),
+    }
+}
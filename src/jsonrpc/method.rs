@@ -0,0 +1,164 @@
+use crate::{
+    jsonrpc::{
+        CancelTaskParams, DeleteTaskPushNotificationConfigParams,
+        GetTaskPushNotificationConfigParams, JsonRpcId, JsonRpcMethods, JsonRpcPayload,
+        JsonRpcRequest, JsonRpcResponse, ListTaskPushNotificationConfigParams, ListTasksParams,
+        ListTasksResult, MessageSendParams, SendMessageResult, SendStreamingMessageSuccessResponse,
+        TaskIdParams, TaskQueryParams,
+    },
+    AgentCard, Task, TaskPushNotificationConfig,
+};
+
+/// Links an A2A JSON-RPC method to the types of its `params` and `result` members
+/// so that requests and responses for a method are checked at compile time.
+///
+/// Each A2A method is represented by a unit struct of the same name as its [JsonRpcMethods] variant.
+/// ```
+/// use agentic_types::jsonrpc::{A2aMethod, JsonRpcId, TaskQueryParams, TasksGet};
+///
+/// let request = TasksGet::request(TaskQueryParams::new("task-1"), JsonRpcId::Number(1));
+/// assert_eq!(request.method.as_str(), "tasks/get");
+/// ```
+pub trait A2aMethod<'a> {
+    /// The method name sent in the `method` member of the request.
+    const METHOD: JsonRpcMethods;
+    /// The type of the `params` member of the request.
+    type Params;
+    /// The type of the `result` member of a successful response.
+    type Result;
+
+    /// Build a request for this method.
+    fn request(params: Self::Params, id: JsonRpcId) -> JsonRpcRequest<'a, Self::Params> {
+        JsonRpcRequest {
+            jsonrpc: "2.0",
+            method: Self::METHOD,
+            params: Some(params),
+            id: Some(id),
+        }
+    }
+
+    /// Build a successful response for this method.
+    fn response(result: Self::Result, id: JsonRpcId) -> JsonRpcResponse<'a, Self::Result> {
+        JsonRpcResponse {
+            jsonrpc: "2.0",
+            id: Some(id),
+            payload: JsonRpcPayload::Success { result },
+        }
+    }
+}
+
+/// A request whose `params` are checked against the method `M`.
+pub type A2aRequest<'a, M> = JsonRpcRequest<'a, <M as A2aMethod<'a>>::Params>;
+
+/// A response whose `result` is checked against the method `M`.
+pub type A2aResponse<'a, M> = JsonRpcResponse<'a, <M as A2aMethod<'a>>::Result>;
+
+/// `message/send`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct MessageSend;
+
+impl<'a> A2aMethod<'a> for MessageSend {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::MessageSend;
+    type Params = MessageSendParams<'a>;
+    type Result = SendMessageResult<'a>;
+}
+
+/// `message/stream`. Each server-sent event carries one [SendStreamingMessageSuccessResponse].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct MessageStream;
+
+impl<'a> A2aMethod<'a> for MessageStream {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::MessageStream;
+    type Params = MessageSendParams<'a>;
+    type Result = SendStreamingMessageSuccessResponse<'a>;
+}
+
+/// `tasks/get`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct TasksGet;
+
+impl<'a> A2aMethod<'a> for TasksGet {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::TasksGet;
+    type Params = TaskQueryParams<'a>;
+    type Result = Task<'a>;
+}
+
+/// `tasks/list`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct TasksList;
+
+impl<'a> A2aMethod<'a> for TasksList {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::TasksList;
+    type Params = ListTasksParams<'a>;
+    type Result = ListTasksResult<'a>;
+}
+
+/// `tasks/cancel`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct TasksCancel;
+
+impl<'a> A2aMethod<'a> for TasksCancel {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::TasksCancel;
+    type Params = CancelTaskParams<'a>;
+    type Result = Task<'a>;
+}
+
+/// `tasks/pushNotificationConfig/set`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct TasksPushNotificationConfigSet;
+
+impl<'a> A2aMethod<'a> for TasksPushNotificationConfigSet {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::TasksPushNotificationConfigSet;
+    type Params = TaskPushNotificationConfig<'a>;
+    type Result = TaskPushNotificationConfig<'a>;
+}
+
+/// `tasks/pushNotificationConfig/get`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct TasksPushNotificationConfigGet;
+
+impl<'a> A2aMethod<'a> for TasksPushNotificationConfigGet {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::TasksPushNotificationConfigGet;
+    type Params = GetTaskPushNotificationConfigParams<'a>;
+    type Result = TaskPushNotificationConfig<'a>;
+}
+
+/// `tasks/pushNotificationConfig/list`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct TasksPushNotificationConfigList;
+
+impl<'a> A2aMethod<'a> for TasksPushNotificationConfigList {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::TasksPushNotificationConfigList;
+    type Params = ListTaskPushNotificationConfigParams<'a>;
+    type Result = Vec<TaskPushNotificationConfig<'a>>;
+}
+
+/// `tasks/pushNotificationConfig/delete`. A successful response has a `null` result.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct TasksPushNotificationConfigDelete;
+
+impl<'a> A2aMethod<'a> for TasksPushNotificationConfigDelete {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::TasksPushNotificationConfigDelete;
+    type Params = DeleteTaskPushNotificationConfigParams<'a>;
+    type Result = ();
+}
+
+/// `tasks/resubscribe`. Each server-sent event carries one [SendStreamingMessageSuccessResponse].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct TasksResubscribe;
+
+impl<'a> A2aMethod<'a> for TasksResubscribe {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::TasksResubscribe;
+    type Params = TaskIdParams<'a>;
+    type Result = SendStreamingMessageSuccessResponse<'a>;
+}
+
+/// `agent/getAuthenticatedExtendedCard`. The method takes no params.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct AgentGetAuthenticatedExtendedCard;
+
+impl<'a> A2aMethod<'a> for AgentGetAuthenticatedExtendedCard {
+    const METHOD: JsonRpcMethods = JsonRpcMethods::AgentGetAuthenticatedExtendedCard;
+    type Params = ();
+    type Result = AgentCard<'a>;
+}
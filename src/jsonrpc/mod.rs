@@ -0,0 +1,1149 @@
+use std::{borrow::Cow, fmt};
+
+use serde::{
+    de::{self, IntoDeserializer},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+mod method;
+pub use method::*;
+
+mod router;
+pub use router::*;
+
+mod validation;
+pub use validation::*;
+
+mod reducer;
+pub use reducer::*;
+
+mod chunking;
+pub use chunking::*;
+
+pub use crate::JsonRpcError;
+
+use crate::{
+    impl_into_owned, AgentCard, Artifact, ArtifactUpdateKind, IntoOwned, JsonRpcErrorCode,
+    JsonStrMemKV, JsonValue, JsonValueDeserializer, MediaType, Message, MessageKind,
+    MissingFieldError, PushNotificationConfig, StatusUpdateKind, Task, TaskKind, TaskProjection,
+    TaskState, TaskStatus,
+};
+
+/// A2A adheres to the standard JSON-RPC 2.0 structures for requests and responses.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct JsonRpcRequest<'a, T> {
+    /// A String specifying the version of the JSON-RPC protocol. MUST be exactly "2.0".
+    pub jsonrpc: &'a str,
+    /// A String containing the name of the method to be invoked (e.g., "message/send", "tasks/get").
+    pub method: JsonRpcMethods,
+    /// A Structured value that holds the parameter values to be used during the invocation of the method. This member MAY be omitted if the method expects no parameters. A2A methods typically use an object for params.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<T>,
+    /// An identifier established by the Client that MUST contain a String, Number, or NULL value if included.
+    /// If it is not included it is assumed to be a notification. The value SHOULD NOT be NULL for requests expecting a response,
+    /// and Numbers SHOULD NOT contain fractional parts. The Server MUST reply with the same value in the Response object
+    /// if included. This member is used to correlate the context between the two objects.
+    /// A2A methods typically expect a response or stream, so id will usually be present and non-null.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<JsonRpcId>,
+}
+
+/// The id of a JSON-RPC request, which is a String, a Number without a fractional part, or NULL.
+#[derive(Debug, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[serde(untagged)]
+pub enum JsonRpcId {
+    String(String),
+    Number(i64),
+    Null,
+}
+
+impl<'de> Deserialize<'de> for JsonRpcId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct JsonRpcIdVisitor;
+
+        impl<'de> de::Visitor<'de> for JsonRpcIdVisitor {
+            type Value = JsonRpcId;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string, an integer or null as the JSON-RPC id")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(JsonRpcId::String(value.to_owned()))
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+                Ok(JsonRpcId::String(value))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(JsonRpcId::Number(value))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                i64::try_from(value)
+                    .map(JsonRpcId::Number)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                Err(E::invalid_value(de::Unexpected::Float(value), &self))
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(JsonRpcId::Null)
+            }
+
+            fn visit_none<E>(self) -> Result<Self::Value, E> {
+                Ok(JsonRpcId::Null)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                JsonRpcId::deserialize(deserializer)
+            }
+        }
+
+        deserializer.deserialize_any(JsonRpcIdVisitor)
+    }
+}
+
+/// The A2A Server's HTTP response body MUST be a JSONRPCResponse object
+///  (or, for streaming methods, an SSE stream where each event's data is a JSONRPCResponse).
+/// The Content-Type for JSON-RPC responses is `application/json`. For SSE streams, it is `text/event-stream`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct JsonRpcResponse<'a, T> {
+    /// A String specifying the version of the JSON-RPC protocol. MUST be exactly "2.0".
+    #[serde(borrow)]
+    pub jsonrpc: &'a str,
+    /// This member is REQUIRED. It MUST be the same as the value of the id member in the Request Object.
+    /// If there was an error in detecting the id in the Request object (e.g. Parse error/Invalid Request), it MUST be null.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<JsonRpcId>,
+    /// [JsonRpcPayload] representing a success or error
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub payload: JsonRpcPayload<'a, T>,
+}
+
+/// A JSON-RPC 2.0 batch, sent as an array of request objects and answered with an array of
+/// response objects. Notifications get no entry in the response array and an empty array is an invalid request.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[serde(untagged)]
+pub enum JsonRpcBatch<T> {
+    /// A single request or response object.
+    Single(T),
+    /// An array of request or response objects.
+    Batch(Vec<T>),
+}
+
+impl<T> JsonRpcBatch<T> {
+    /// The number of request or response objects.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Single(_) => 1,
+            Self::Batch(batch) => batch.len(),
+        }
+    }
+
+    /// `true` for an empty batch, which is an invalid request.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The messages in order, a single message becoming a batch of one.
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::Single(single) => vec![single],
+            Self::Batch(batch) => batch,
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for JsonRpcBatch<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self::Batch(value)
+    }
+}
+
+/// A single request or a batch of requests.
+pub type JsonRpcBatchRequest<'a, T> = JsonRpcBatch<JsonRpcRequest<'a, T>>;
+
+/// A single response or a batch of responses.
+pub type JsonRpcBatchResponse<'a, T> = JsonRpcBatch<JsonRpcResponse<'a, T>>;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[serde(untagged)]
+pub enum JsonRpcPayload<'a, T> {
+    Success {
+        result: T,
+    },
+    Error {
+        #[serde(borrow)]
+        error: JsonRpcError<'a>,
+    },
+}
+
+/// The A2A methods exposed over JSON-RPC 2.0.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum JsonRpcMethods {
+    /// `message/send` POST method
+    MessageSend,
+    /// `message/stream` POST method
+    MessageStream,
+    /// `tasks/get` POST method
+    TasksGet,
+    /// `tasks/list` POST method
+    TasksList,
+    /// `tasks/cancel` POST method
+    TasksCancel,
+    /// `tasks/pushNotificationConfig/set` POST method
+    TasksPushNotificationConfigSet,
+    /// `tasks/pushNotificationConfig/get` POST method
+    TasksPushNotificationConfigGet,
+    /// `tasks/pushNotificationConfig/list` POST method
+    TasksPushNotificationConfigList,
+    /// `tasks/pushNotificationConfig/delete` POST method
+    TasksPushNotificationConfigDelete,
+    /// `tasks/resubscribe` POST method
+    TasksResubscribe,
+    /// `agent/getAuthenticatedExtendedCard` POST method
+    AgentGetAuthenticatedExtendedCard,
+}
+
+impl JsonRpcMethods {
+    /// Every method defined by the A2A JSON-RPC binding.
+    pub const ALL: [Self; 11] = [
+        Self::MessageSend,
+        Self::MessageStream,
+        Self::TasksGet,
+        Self::TasksList,
+        Self::TasksCancel,
+        Self::TasksPushNotificationConfigSet,
+        Self::TasksPushNotificationConfigGet,
+        Self::TasksPushNotificationConfigList,
+        Self::TasksPushNotificationConfigDelete,
+        Self::TasksResubscribe,
+        Self::AgentGetAuthenticatedExtendedCard,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MessageSend => "message/send",
+            Self::MessageStream => "message/stream",
+            Self::TasksGet => "tasks/get",
+            Self::TasksList => "tasks/list",
+            Self::TasksCancel => "tasks/cancel",
+            Self::TasksPushNotificationConfigSet => "tasks/pushNotificationConfig/set",
+            Self::TasksPushNotificationConfigGet => "tasks/pushNotificationConfig/get",
+            Self::TasksPushNotificationConfigList => "tasks/pushNotificationConfig/list",
+            Self::TasksPushNotificationConfigDelete => "tasks/pushNotificationConfig/delete",
+            Self::TasksResubscribe => "tasks/resubscribe",
+            Self::AgentGetAuthenticatedExtendedCard => "agent/getAuthenticatedExtendedCard",
+        }
+    }
+}
+
+impl fmt::Display for JsonRpcMethods {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for JsonRpcMethods {
+    type Error = JsonRpcError<'a>;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+
+        Self::ALL
+            .into_iter()
+            .find(|method| method.as_str() == value)
+            .ok_or_else(|| {
+                JsonRpcError::new(JsonRpcErrorCode::MethodNotFound)
+                    .with_message(format!("The method `{value}` is not an A2A method"))
+            })
+    }
+}
+
+impl Serialize for JsonRpcMethods {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonRpcMethods {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Cow::<'de, str>::deserialize(deserializer)?;
+
+        Self::try_from(value.as_ref()).map_err(|error| de::Error::custom(error.message))
+    }
+}
+
+/// Defines the parameters for a request to send a message to an agent. This can be used
+/// to create a new task, continue an existing one, or restart a task.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+pub struct MessageSendParams<'a> {
+    /// The message object being sent to the agent.
+    #[serde(borrow)]
+    pub message: Message<'a>,
+    /// Optional configuration for the send request.
+    pub configuration: Option<MessageSendConfiguration<'a>>,
+    /// Optional metadata for extensions.
+    pub metadata: Option<JsonStrMemKV<'a>>,
+}
+
+/// Defines configuration options for a `message/send` or `message/stream` request.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageSendConfiguration<'a> {
+    ///  A list of output MIME types the client is prepared to accept in the response.
+    #[serde(borrow)]
+    accepted_output_modes: Option<Vec<MediaType<'a>>>,
+    ///  The number of most recent messages from the task's history to retrieve in the response.
+    history_length: Option<i16>,
+    ///  Configuration for the agent to send push notifications for updates after the initial response.
+    push_notification_config: Option<PushNotificationConfig<'a>>,
+    ///  If true, the client will wait for the task to complete. The server may reject this if the task is long-running.
+    blocking: Option<bool>,
+}
+
+impl<'a> MessageSendConfiguration<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_accepted_output_modes<I>(mut self, accepted_output_modes: I) -> Self
+    where
+        I: IntoIterator<Item = MediaType<'a>>,
+    {
+        self.accepted_output_modes = Some(accepted_output_modes.into_iter().collect());
+
+        self
+    }
+
+    pub fn with_history_length(mut self, history_length: i16) -> Self {
+        self.history_length = Some(history_length);
+
+        self
+    }
+
+    pub fn with_push_notification_config(
+        mut self,
+        push_notification_config: PushNotificationConfig<'a>,
+    ) -> Self {
+        self.push_notification_config = Some(push_notification_config);
+
+        self
+    }
+
+    pub fn with_blocking(mut self, blocking: bool) -> Self {
+        self.blocking = Some(blocking);
+
+        self
+    }
+
+    pub fn accepted_output_modes(&self) -> Option<&[MediaType<'a>]> {
+        self.accepted_output_modes.as_deref()
+    }
+
+    pub fn history_length(&self) -> Option<i16> {
+        self.history_length
+    }
+
+    pub fn push_notification_config(&self) -> Option<&PushNotificationConfig<'a>> {
+        self.push_notification_config.as_ref()
+    }
+
+    pub fn blocking(&self) -> Option<bool> {
+        self.blocking
+    }
+
+    /// The task returned for the message, with at most `historyLength` messages of its history.
+    pub fn projection(&self) -> Result<TaskProjection, JsonRpcError<'static>> {
+        TaskProjection::new(self.history_length.map(i64::from), true)
+    }
+}
+
+/// The result of a `message/send` request, either a direct [Message] reply or the [Task] created for it.
+/// Decoded by its `kind` member, `message` or `task`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize)]
+#[serde(untagged)]
+pub enum SendMessageResult<'a> {
+    Task(Task<'a>),
+    Message(Message<'a>),
+}
+
+impl<'a> SendMessageResult<'a> {
+    /// The `kind` discriminator of the result.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Task(_) => TaskKind::VALUE,
+            Self::Message(_) => MessageKind::VALUE,
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for SendMessageResult<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const KINDS: &[&str] = &[MessageKind::VALUE, TaskKind::VALUE];
+
+        let (kind, value) = split_kind(JsonValue::deserialize(deserializer)?, KINDS)?;
+
+        match kind {
+            MessageKind::VALUE => Message::deserialize(value).map(Self::Message),
+            _ => Task::deserialize(value).map(Self::Task),
+        }
+    }
+}
+
+/// Represents a successful JSON-RPC response for the `message/stream` method.
+/// The server may send multiple response objects for a single request.
+/// The result, which can be a Message, Task, or a streaming update event.
+/// Decoded by its `kind` member, `message`, `task`, `status-update` or `artifact-update`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize)]
+#[serde(untagged)]
+pub enum SendStreamingMessageSuccessResponse<'a> {
+    Message(Message<'a>),
+    Task(Task<'a>),
+    TaskStatusUpdateEvent(TaskStatusUpdateEvent<'a>),
+    TaskArtifactUpdateEvent(TaskArtifactUpdateEvent<'a>),
+}
+
+impl<'a> SendStreamingMessageSuccessResponse<'a> {
+    /// The `kind` discriminator of the result.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Message(_) => MessageKind::VALUE,
+            Self::Task(_) => TaskKind::VALUE,
+            Self::TaskStatusUpdateEvent(_) => StatusUpdateKind::VALUE,
+            Self::TaskArtifactUpdateEvent(_) => ArtifactUpdateKind::VALUE,
+        }
+    }
+}
+
+impl<'a> From<SendMessageResult<'a>> for SendStreamingMessageSuccessResponse<'a> {
+    fn from(value: SendMessageResult<'a>) -> Self {
+        match value {
+            SendMessageResult::Task(task) => Self::Task(task),
+            SendMessageResult::Message(message) => Self::Message(message),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for SendStreamingMessageSuccessResponse<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const KINDS: &[&str] = &[
+            MessageKind::VALUE,
+            TaskKind::VALUE,
+            StatusUpdateKind::VALUE,
+            ArtifactUpdateKind::VALUE,
+        ];
+
+        let (kind, value) = split_kind(JsonValue::deserialize(deserializer)?, KINDS)?;
+
+        match kind {
+            MessageKind::VALUE => Message::deserialize(value).map(Self::Message),
+            TaskKind::VALUE => Task::deserialize(value).map(Self::Task),
+            StatusUpdateKind::VALUE => {
+                TaskStatusUpdateEvent::deserialize(value).map(Self::TaskStatusUpdateEvent)
+            }
+            _ => TaskArtifactUpdateEvent::deserialize(value).map(Self::TaskArtifactUpdateEvent),
+        }
+    }
+}
+
+/// Read the `kind` member of a result object, which has to be one of `kinds`,
+/// and hand back a deserializer for the whole object.
+fn split_kind<'de, E: de::Error>(
+    value: JsonValue<'de>,
+    kinds: &'static [&'static str],
+) -> Result<(&'static str, JsonValueDeserializer<'de, E>), E> {
+    let kind = match value.get("kind") {
+        Some(JsonValue::String(kind)) => kinds
+            .iter()
+            .find(|known| **known == kind.as_ref())
+            .ok_or_else(|| E::unknown_variant(kind, kinds))?,
+        Some(_) => return Err(E::custom("the `kind` member must be a string")),
+        None => return Err(E::missing_field("kind")),
+    };
+
+    Ok((kind, value.into_deserializer()))
+}
+
+/// Carries information about a change in the task's status during streaming.
+/// This is one of the possible result types in a SendStreamingMessageSuccessResponse.
+/// An event sent by the agent to notify the client of a change in a task's status.
+/// This is typically used in streaming or subscription models.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStatusUpdateEvent<'a> {
+    /// The ID of the task that was updated.
+    #[serde(borrow)]
+    task_id: Cow<'a, str>,
+    /// The context ID associated with the task.
+    #[serde(borrow)]
+    context_id: Cow<'a, str>,
+    /// The type of this event, used as a discriminator. Always 'status-update'.
+    kind: StatusUpdateKind,
+    /// The new status of the task.
+    status: TaskStatus<'a>,
+    /// If true, this is the final event in the stream for this interaction.
+    r#final: bool,
+    /// Optional metadata for extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<JsonStrMemKV<'a>>,
+}
+
+impl<'a> TaskStatusUpdateEvent<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start building an event. `taskId` and `contextId` are required.
+    pub fn builder() -> TaskStatusUpdateEventBuilder<'a> {
+        TaskStatusUpdateEventBuilder { event: Self::new() }
+    }
+
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    pub fn context_id(&self) -> &str {
+        &self.context_id
+    }
+
+    pub fn kind(&self) -> &'static str {
+        self.kind.as_str()
+    }
+
+    pub fn status(&self) -> &TaskStatus<'a> {
+        &self.status
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.r#final
+    }
+
+    pub fn metadata(&self) -> Option<&JsonStrMemKV<'a>> {
+        self.metadata.as_ref()
+    }
+}
+
+/// Builds a [TaskStatusUpdateEvent], checking at [TaskStatusUpdateEventBuilder::build] that the required fields are set.
+#[derive(Debug, Clone)]
+pub struct TaskStatusUpdateEventBuilder<'a> {
+    event: TaskStatusUpdateEvent<'a>,
+}
+
+impl<'a> TaskStatusUpdateEventBuilder<'a> {
+    pub fn task_id(mut self, task_id: impl Into<Cow<'a, str>>) -> Self {
+        self.event.task_id = task_id.into();
+
+        self
+    }
+
+    pub fn context_id(mut self, context_id: impl Into<Cow<'a, str>>) -> Self {
+        self.event.context_id = context_id.into();
+
+        self
+    }
+
+    pub fn status(mut self, status: TaskStatus<'a>) -> Self {
+        self.event.status = status;
+
+        self
+    }
+
+    /// Mark the event as the last one of the stream.
+    pub fn r#final(mut self, r#final: bool) -> Self {
+        self.event.r#final = r#final;
+
+        self
+    }
+
+    pub fn metadata(mut self, metadata: JsonStrMemKV<'a>) -> Self {
+        self.event.metadata = Some(metadata);
+
+        self
+    }
+
+    pub fn build(self) -> Result<TaskStatusUpdateEvent<'a>, MissingFieldError> {
+        let event = self.event;
+
+        MissingFieldError::check(
+            "TaskStatusUpdateEvent",
+            [
+                ("taskId", !event.task_id.is_empty()),
+                ("contextId", !event.context_id.is_empty()),
+            ],
+        )?;
+
+        Ok(event)
+    }
+}
+
+/// Carries a new or updated artifact (or a chunk of an artifact) generated by the task during streaming.
+/// This is one of the possible result types in a SendTaskStreamingResponse.
+/// An event sent by the agent to notify the client that an artifact has been
+/// generated or updated. This is typically used in streaming models.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskArtifactUpdateEvent<'a> {
+    ///  The ID of the task this artifact belongs to.
+    #[serde(borrow)]
+    task_id: Cow<'a, str>,
+    /// The context ID associated with the task.
+    #[serde(borrow)]
+    context_id: Cow<'a, str>,
+    /// The type of this event, used as a discriminator. Always 'artifact-update'.
+    kind: ArtifactUpdateKind,
+    /// The artifact that was generated or updated.
+    artifact: Artifact<'a>,
+    /// If true, the content of this artifact should be appended to a previously sent artifact with the same ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    append: Option<bool>,
+    /// If true, this is the final chunk of the artifact.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_chunk: Option<bool>,
+    /// Optional metadata for extensions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<JsonStrMemKV<'a>>,
+}
+
+impl<'a> TaskArtifactUpdateEvent<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start building an event. `taskId`, `contextId` and the artifact are required.
+    pub fn builder() -> TaskArtifactUpdateEventBuilder<'a> {
+        TaskArtifactUpdateEventBuilder { event: Self::new() }
+    }
+
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    pub fn context_id(&self) -> &str {
+        &self.context_id
+    }
+
+    pub fn kind(&self) -> &'static str {
+        self.kind.as_str()
+    }
+
+    pub fn artifact(&self) -> &Artifact<'a> {
+        &self.artifact
+    }
+
+    pub fn append(&self) -> Option<bool> {
+        self.append
+    }
+
+    pub fn last_chunk(&self) -> Option<bool> {
+        self.last_chunk
+    }
+
+    pub fn metadata(&self) -> Option<&JsonStrMemKV<'a>> {
+        self.metadata.as_ref()
+    }
+}
+
+/// Builds a [TaskArtifactUpdateEvent], checking at [TaskArtifactUpdateEventBuilder::build] that the required fields are set.
+#[derive(Debug, Clone)]
+pub struct TaskArtifactUpdateEventBuilder<'a> {
+    event: TaskArtifactUpdateEvent<'a>,
+}
+
+impl<'a> TaskArtifactUpdateEventBuilder<'a> {
+    pub fn task_id(mut self, task_id: impl Into<Cow<'a, str>>) -> Self {
+        self.event.task_id = task_id.into();
+
+        self
+    }
+
+    pub fn context_id(mut self, context_id: impl Into<Cow<'a, str>>) -> Self {
+        self.event.context_id = context_id.into();
+
+        self
+    }
+
+    pub fn artifact(mut self, artifact: Artifact<'a>) -> Self {
+        self.event.artifact = artifact;
+
+        self
+    }
+
+    /// Whether the parts are appended to a previously sent artifact with the same id.
+    pub fn append(mut self, append: bool) -> Self {
+        self.event.append = Some(append);
+
+        self
+    }
+
+    pub fn last_chunk(mut self, last_chunk: bool) -> Self {
+        self.event.last_chunk = Some(last_chunk);
+
+        self
+    }
+
+    pub fn metadata(mut self, metadata: JsonStrMemKV<'a>) -> Self {
+        self.event.metadata = Some(metadata);
+
+        self
+    }
+
+    pub fn build(self) -> Result<TaskArtifactUpdateEvent<'a>, MissingFieldError> {
+        let event = self.event;
+
+        MissingFieldError::check(
+            "TaskArtifactUpdateEvent",
+            [
+                ("taskId", !event.task_id.is_empty()),
+                ("contextId", !event.context_id.is_empty()),
+                ("artifact", !event.artifact.artifact_id().is_empty()),
+            ],
+        )?;
+
+        Ok(event)
+    }
+}
+
+/// Defines parameters containing a task ID, used for simple task operations.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+pub struct TaskIdParams<'a> {
+    /// The unique identifier (e.g. UUID) of the task.
+    #[serde(borrow)]
+    pub id: Cow<'a, str>,
+    /// Optional metadata associated with the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<JsonStrMemKV<'a>>,
+}
+
+impl<'a> TaskIdParams<'a> {
+    pub fn new(id: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            id: id.into(),
+            metadata: None,
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: JsonStrMemKV<'a>) -> Self {
+        self.metadata = Some(metadata);
+
+        self
+    }
+}
+
+/// Used as the params object for the `tasks/cancel` method.
+pub type CancelTaskParams<'a> = TaskIdParams<'a>;
+
+/// Retrieves the current state (including status, artifacts, and optionally history) of a previously initiated task.
+/// This is typically used for polling the status of a task initiated with message/send,
+/// or for fetching the final state of a task after being notified via a push notification or after an SSE stream has ended.
+/// Defines parameters for querying a task, with an option to limit history length.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskQueryParams<'a> {
+    /// The ID and metadata of the task to query.
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub base: TaskIdParams<'a>,
+    /// The number of most recent messages from the task's history to retrieve.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_length: Option<i64>,
+}
+
+impl<'a> TaskQueryParams<'a> {
+    pub fn new(id: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            base: TaskIdParams::new(id),
+            history_length: None,
+        }
+    }
+
+    pub fn with_history_length(mut self, history_length: i64) -> Self {
+        self.history_length = Some(history_length);
+
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.base.id
+    }
+
+    pub fn history_length(&self) -> Option<i64> {
+        self.history_length
+    }
+
+    /// The whole task with at most `historyLength` messages of its history.
+    pub fn projection(&self) -> Result<TaskProjection, JsonRpcError<'static>> {
+        TaskProjection::new(self.history_length, true)
+    }
+}
+
+/// Parameters for filtering and paginating task results.
+/// Parameters for listing tasks with optional filtering criteria. @since 0.4.0 */
+/// Note on includeArtifacts parameter: When includeArtifacts is false (the default),
+/// the artifacts field MUST be omitted entirely from each Task object in the response.
+/// The field should not be present as an empty array or null value. When includeArtifacts is true,
+/// the artifacts field should be included with its actual content (which may be an empty array if the task has no artifacts).
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTasksParams<'a> {
+    /// Filter tasks by context ID to get tasks from a specific conversation or session.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    context_id: Option<Cow<'a, str>>,
+    ///Filter tasks by their current status state.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<TaskState>,
+    ///Maximum number of tasks to return. Must be between 1 and 100. Defaults to 50 if not specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_size: Option<i64>,
+    ///Token for pagination. Use the nextPageToken from a previous ListTasksResult response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    page_token: Option<Cow<'a, str>>,
+    ///Number of recent messages to include in each task's history. Must be non-negative. Defaults to 0 if not specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    history_length: Option<i64>,
+    ///Filter tasks updated after this timestamp (milliseconds since epoch). Only tasks with a last updated time greater than or equal to this value will be returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_updated_after: Option<i64>,
+    ///Whether to include artifacts in the returned tasks. Defaults to false to reduce payload size.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    include_artifacts: Option<bool>,
+    ///Request-specific metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<JsonStrMemKV<'a>>,
+}
+
+impl<'a> ListTasksParams<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_context_id(mut self, context_id: impl Into<Cow<'a, str>>) -> Self {
+        self.context_id = Some(context_id.into());
+
+        self
+    }
+
+    pub fn with_status(mut self, status: TaskState) -> Self {
+        self.status = Some(status);
+
+        self
+    }
+
+    pub fn with_page_size(mut self, page_size: i64) -> Self {
+        self.page_size = Some(page_size);
+
+        self
+    }
+
+    pub fn with_page_token(mut self, page_token: impl Into<Cow<'a, str>>) -> Self {
+        self.page_token = Some(page_token.into());
+
+        self
+    }
+
+    pub fn with_history_length(mut self, history_length: i64) -> Self {
+        self.history_length = Some(history_length);
+
+        self
+    }
+
+    pub fn with_last_updated_after(mut self, last_updated_after: i64) -> Self {
+        self.last_updated_after = Some(last_updated_after);
+
+        self
+    }
+
+    pub fn with_include_artifacts(mut self, include_artifacts: bool) -> Self {
+        self.include_artifacts = Some(include_artifacts);
+
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: JsonStrMemKV<'a>) -> Self {
+        self.metadata = Some(metadata);
+
+        self
+    }
+
+    pub fn context_id(&self) -> Option<&str> {
+        self.context_id.as_deref()
+    }
+
+    pub fn status(&self) -> Option<TaskState> {
+        self.status
+    }
+
+    pub fn page_size(&self) -> Option<i64> {
+        self.page_size
+    }
+
+    pub fn page_token(&self) -> Option<&str> {
+        self.page_token.as_deref()
+    }
+
+    pub fn history_length(&self) -> Option<i64> {
+        self.history_length
+    }
+
+    pub fn last_updated_after(&self) -> Option<i64> {
+        self.last_updated_after
+    }
+
+    pub fn include_artifacts(&self) -> Option<bool> {
+        self.include_artifacts
+    }
+
+    pub fn metadata(&self) -> Option<&JsonStrMemKV<'a>> {
+        self.metadata.as_ref()
+    }
+
+    /// Each listed task with `historyLength` messages of its history, none by default,
+    /// and its artifacts only when `includeArtifacts` is set.
+    pub fn projection(&self) -> Result<TaskProjection, JsonRpcError<'static>> {
+        TaskProjection::new(
+            Some(self.history_length.unwrap_or_default()),
+            self.include_artifacts.unwrap_or_default(),
+        )
+    }
+}
+
+/// Result object containing the filtered tasks and pagination information.
+/// Note on nextPageToken: The nextPageToken field MUST always be present in the response.
+/// When there are no more results to retrieve (i.e., this is the final page), the field MUST be set to an empty string ("").
+/// Clients should check for an empty string to determine if more pages are available.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTasksResult<'a> {
+    /// Array of tasks matching the specified criteria.
+    #[serde(borrow)]
+    pub tasks: Vec<Task<'a>>,
+    /// Total number of tasks available (before pagination).
+    pub total_size: i64,
+    /// Maximum number of tasks returned in this response.
+    pub page_size: i64,
+    /// Token for retrieving the next page. Empty string if no more results.
+    #[serde(borrow)]
+    pub next_page_token: Cow<'a, str>,
+}
+
+/// Defines parameters for fetching a specific push notification configuration for a task.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTaskPushNotificationConfigParams<'a> {
+    /// The ID and metadata of the task.
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub base: TaskIdParams<'a>,
+    /// The ID of the push notification configuration to retrieve.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    pub push_notification_config_id: Option<Cow<'a, str>>,
+}
+
+impl<'a> GetTaskPushNotificationConfigParams<'a> {
+    pub fn new(id: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            base: TaskIdParams::new(id),
+            push_notification_config_id: None,
+        }
+    }
+
+    pub fn with_push_notification_config_id(
+        mut self,
+        push_notification_config_id: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.push_notification_config_id = Some(push_notification_config_id.into());
+
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.base.id
+    }
+}
+
+/// Defines parameters for listing all push notification configurations associated with a task.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+pub struct ListTaskPushNotificationConfigParams<'a> {
+    /// The ID and metadata of the task.
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub base: TaskIdParams<'a>,
+}
+
+impl<'a> ListTaskPushNotificationConfigParams<'a> {
+    pub fn new(id: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            base: TaskIdParams::new(id),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.base.id
+    }
+}
+
+/// Defines parameters for deleting a specific push notification configuration for a task.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTaskPushNotificationConfigParams<'a> {
+    /// The ID and metadata of the task.
+    #[serde(flatten)]
+    #[serde(borrow)]
+    pub base: TaskIdParams<'a>,
+    /// The ID of the push notification configuration to delete.
+    #[serde(borrow)]
+    pub push_notification_config_id: Cow<'a, str>,
+}
+
+impl<'a> DeleteTaskPushNotificationConfigParams<'a> {
+    pub fn new(
+        id: impl Into<Cow<'a, str>>,
+        push_notification_config_id: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        Self {
+            base: TaskIdParams::new(id),
+            push_notification_config_id: push_notification_config_id.into(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.base.id
+    }
+}
+
+/// Represents a successful JSON-RPC response for the `agent/getAuthenticatedExtendedCard` method.
+pub struct GetAuthenticatedExtendedCardSuccessResponse<'a> {
+    /// The result is an Agent Card object.
+    pub result: AgentCard<'a>,
+}
+
+impl<'a, T: IntoOwned> IntoOwned for JsonRpcPayload<'a, T> {
+    type Owned = JsonRpcPayload<'static, T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Self::Success { result } => JsonRpcPayload::Success {
+                result: result.into_owned(),
+            },
+            Self::Error { error } => JsonRpcPayload::Error {
+                error: error.into_owned(),
+            },
+        }
+    }
+}
+
+impl<'a> IntoOwned for SendMessageResult<'a> {
+    type Owned = SendMessageResult<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Self::Task(task) => SendMessageResult::Task(task.into_owned()),
+            Self::Message(message) => SendMessageResult::Message(message.into_owned()),
+        }
+    }
+}
+
+impl<'a> IntoOwned for SendStreamingMessageSuccessResponse<'a> {
+    type Owned = SendStreamingMessageSuccessResponse<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Self::Message(message) => {
+                SendStreamingMessageSuccessResponse::Message(message.into_owned())
+            }
+            Self::Task(task) => SendStreamingMessageSuccessResponse::Task(task.into_owned()),
+            Self::TaskStatusUpdateEvent(event) => {
+                SendStreamingMessageSuccessResponse::TaskStatusUpdateEvent(event.into_owned())
+            }
+            Self::TaskArtifactUpdateEvent(event) => {
+                SendStreamingMessageSuccessResponse::TaskArtifactUpdateEvent(event.into_owned())
+            }
+        }
+    }
+}
+
+impl_into_owned!(MessageSendParams {
+    message,
+    configuration,
+    metadata,
+});
+impl_into_owned!(MessageSendConfiguration {
+    accepted_output_modes,
+    history_length,
+    push_notification_config,
+    blocking,
+});
+impl_into_owned!(TaskStatusUpdateEvent {
+    task_id,
+    context_id,
+    kind,
+    status,
+    r#final,
+    metadata,
+});
+impl_into_owned!(TaskArtifactUpdateEvent {
+    task_id,
+    context_id,
+    kind,
+    artifact,
+    append,
+    last_chunk,
+    metadata,
+});
+impl_into_owned!(TaskIdParams { id, metadata });
+impl_into_owned!(TaskQueryParams {
+    base,
+    history_length,
+});
+impl_into_owned!(ListTasksParams {
+    context_id,
+    status,
+    page_size,
+    page_token,
+    history_length,
+    last_updated_after,
+    include_artifacts,
+    metadata,
+});
+impl_into_owned!(ListTasksResult {
+    tasks,
+    total_size,
+    page_size,
+    next_page_token,
+});
+impl_into_owned!(GetTaskPushNotificationConfigParams {
+    base,
+    push_notification_config_id,
+});
+impl_into_owned!(ListTaskPushNotificationConfigParams { base });
+impl_into_owned!(DeleteTaskPushNotificationConfigParams {
+    base,
+    push_notification_config_id,
+});
+impl_into_owned!(GetAuthenticatedExtendedCardSuccessResponse { result });
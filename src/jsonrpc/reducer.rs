@@ -0,0 +1,230 @@
+use std::{borrow::Cow, collections::BTreeMap, fmt};
+
+use crate::{
+    jsonrpc::{
+        chunking::{continue_part, continues_part},
+        SendStreamingMessageSuccessResponse, TaskArtifactUpdateEvent, TaskStatusUpdateEvent,
+    },
+    Message, Task, TaskTransitionError,
+};
+
+/// Folds the results of `message/stream` and `tasks/resubscribe` into the current [Task].
+///
+/// A client starts from the first `Task` of the stream and applies every result after it.
+/// A server can rebuild a task the same way by replaying its event log.
+///
+/// - a status update has to be a legal transition, see [crate::TaskState::can_transition_to].
+///   The message of the status it replaces is moved to `history`
+/// - an artifact update replaces the artifact with the same id, or extends its parts when `append` is set.
+///   A part split by [crate::jsonrpc::ArtifactChunker] is joined again, see [crate::jsonrpc::ARTIFACT_CHUNK_CONTINUES_PART]
+/// - a message is added to `history`
+/// - a task replaces the snapshot
+#[derive(Debug, Clone)]
+pub struct TaskReducer<'a> {
+    task: Task<'a>,
+    /// The `lastChunk` flag of the latest update of each artifact.
+    last_chunks: BTreeMap<Cow<'a, str>, Option<bool>>,
+    finished: bool,
+}
+
+impl<'a> TaskReducer<'a> {
+    pub fn new(task: Task<'a>) -> Self {
+        Self {
+            task,
+            last_chunks: BTreeMap::new(),
+            finished: false,
+        }
+    }
+
+    pub fn task(&self) -> &Task<'a> {
+        &self.task
+    }
+
+    pub fn into_task(self) -> Task<'a> {
+        self.task
+    }
+
+    /// The status update flagged as `final` has been applied. Any further event is rejected.
+    pub fn is_final(&self) -> bool {
+        self.finished
+    }
+
+    /// The ids of the artifacts whose latest update has `lastChunk` set to `false`.
+    pub fn pending_artifacts(&self) -> impl Iterator<Item = &str> {
+        self.last_chunks
+            .iter()
+            .filter(|(_, last_chunk)| **last_chunk == Some(false))
+            .map(|(artifact_id, _)| artifact_id.as_ref())
+    }
+
+    /// Apply one result of the stream. The snapshot is left untouched when an error is returned.
+    pub fn apply(
+        &mut self,
+        event: SendStreamingMessageSuccessResponse<'a>,
+    ) -> Result<(), TaskUpdateError<'a>> {
+        match event {
+            SendStreamingMessageSuccessResponse::Task(task) => self.apply_task(task),
+            SendStreamingMessageSuccessResponse::Message(message) => self.apply_message(message),
+            SendStreamingMessageSuccessResponse::TaskStatusUpdateEvent(event) => {
+                self.apply_status_update(event)
+            }
+            SendStreamingMessageSuccessResponse::TaskArtifactUpdateEvent(event) => {
+                self.apply_artifact_update(event)
+            }
+        }
+    }
+
+    pub fn apply_task(&mut self, task: Task<'a>) -> Result<(), TaskUpdateError<'a>> {
+        self.check_event(task.id())?;
+
+        self.task = task;
+
+        Ok(())
+    }
+
+    /// Add the message to `history` unless a message with the same `messageId` is already there.
+    pub fn apply_message(&mut self, message: Message<'a>) -> Result<(), TaskUpdateError<'a>> {
+        self.check_event(message.task_id.as_deref().unwrap_or(self.task.id()))?;
+
+        self.push_history(message);
+
+        Ok(())
+    }
+
+    pub fn apply_status_update(
+        &mut self,
+        event: TaskStatusUpdateEvent<'a>,
+    ) -> Result<(), TaskUpdateError<'a>> {
+        self.check_event(event.task_id())?;
+
+        let previous = self.task.status().message().cloned();
+        let is_final = event.is_final();
+
+        self.task.apply_status(event.status().clone())?;
+
+        if let Some(message) = previous {
+            self.push_history(message);
+        }
+        self.finished = is_final;
+
+        Ok(())
+    }
+
+    pub fn apply_artifact_update(
+        &mut self,
+        event: TaskArtifactUpdateEvent<'a>,
+    ) -> Result<(), TaskUpdateError<'a>> {
+        self.check_event(event.task_id())?;
+
+        let artifact = event.artifact().clone();
+        let artifact_id = Cow::Owned(artifact.artifact_id().to_owned());
+        let position = self.task.artifacts().and_then(|artifacts| {
+            artifacts
+                .iter()
+                .position(|existing| existing.artifact_id() == artifact_id)
+        });
+
+        match (event.append(), position) {
+            (Some(true), None) => return Err(TaskUpdateError::UnknownArtifact(artifact_id)),
+            (Some(true), Some(_)) if self.last_chunks.get(&artifact_id) == Some(&Some(true)) => {
+                return Err(TaskUpdateError::ArtifactClosed(artifact_id));
+            }
+            (Some(true), Some(index)) => {
+                let parts = self.task.artifacts_mut()[index].parts_mut();
+                let mut chunk = artifact.parts().iter().cloned();
+
+                if continues_part(&event) {
+                    let continued = parts.last_mut().zip(chunk.next());
+
+                    if !continued.is_some_and(|(last, first)| continue_part(last, first)) {
+                        return Err(TaskUpdateError::InvalidContinuation(artifact_id));
+                    }
+                }
+                parts.extend(chunk);
+            }
+            (_, Some(index)) => self.task.artifacts_mut()[index] = artifact,
+            (_, None) => self.task.artifacts_mut().push(artifact),
+        }
+
+        self.last_chunks.insert(artifact_id, event.last_chunk());
+
+        Ok(())
+    }
+
+    fn check_event(&self, task_id: &str) -> Result<(), TaskUpdateError<'a>> {
+        if self.finished {
+            return Err(TaskUpdateError::AfterFinal);
+        }
+
+        if task_id != self.task.id() {
+            return Err(TaskUpdateError::TaskMismatch {
+                expected: Cow::Owned(self.task.id().to_owned()),
+                found: Cow::Owned(task_id.to_owned()),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn push_history(&mut self, message: Message<'a>) {
+        let history = self.task.history_mut();
+
+        if !history
+            .iter()
+            .any(|existing| existing.message_id == message.message_id)
+        {
+            history.push(message);
+        }
+    }
+}
+
+/// An event that cannot be applied to the snapshot of a [TaskReducer].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TaskUpdateError<'a> {
+    /// The event belongs to another task.
+    TaskMismatch {
+        expected: Cow<'a, str>,
+        found: Cow<'a, str>,
+    },
+    /// The status cannot follow the current state of the task.
+    Transition(TaskTransitionError<'a>),
+    /// An `append` update for an artifact that was never sent.
+    UnknownArtifact(Cow<'a, str>),
+    /// An `append` update after the update of the artifact flagged as `lastChunk`.
+    ArtifactClosed(Cow<'a, str>),
+    /// An `append` update flagged as continuing the last part, which is not of the same kind as its first part.
+    InvalidContinuation(Cow<'a, str>),
+    /// An event after the status update flagged as `final`.
+    AfterFinal,
+}
+
+impl<'a> From<TaskTransitionError<'a>> for TaskUpdateError<'a> {
+    fn from(value: TaskTransitionError<'a>) -> Self {
+        Self::Transition(value)
+    }
+}
+
+impl<'a> fmt::Display for TaskUpdateError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TaskMismatch { expected, found } => {
+                write!(f, "Expected an event of task `{expected}`, found `{found}`")
+            }
+            Self::Transition(error) => error.fmt(f),
+            Self::UnknownArtifact(artifact_id) => {
+                write!(f, "Cannot append to the unknown artifact `{artifact_id}`")
+            }
+            Self::ArtifactClosed(artifact_id) => write!(
+                f,
+                "Cannot append to the artifact `{artifact_id}` after its last chunk"
+            ),
+            Self::InvalidContinuation(artifact_id) => write!(
+                f,
+                "The update of the artifact `{artifact_id}` does not continue its last part"
+            ),
+            Self::AfterFinal => f.write_str("Received an event after the final event"),
+        }
+    }
+}
+
+impl<'a> std::error::Error for TaskUpdateError<'a> {}
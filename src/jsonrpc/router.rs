@@ -0,0 +1,412 @@
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+
+use crate::{
+    jsonrpc::{
+        parse_raw, parse_request_envelope, A2aMethod, AgentGetAuthenticatedExtendedCard,
+        CancelTaskParams, DeleteTaskPushNotificationConfigParams,
+        GetTaskPushNotificationConfigParams, JsonRpcError, JsonRpcId, JsonRpcMethods,
+        JsonRpcPayload, JsonRpcResponse, ListTaskPushNotificationConfigParams, ListTasksParams,
+        ListTasksResult, MessageSend, MessageSendParams, MessageStream, SendMessageResult,
+        SendStreamingMessageSuccessResponse, TaskIdParams, TaskQueryParams, TasksCancel, TasksGet,
+        TasksList, TasksPushNotificationConfigDelete, TasksPushNotificationConfigGet,
+        TasksPushNotificationConfigList, TasksPushNotificationConfigSet, TasksResubscribe,
+    },
+    AgentCard, JsonRpcErrorCode, Task, TaskPushNotificationConfig,
+};
+
+/// The outcome of a handler method, where the error is sent back to the client as the `error` member.
+pub type HandlerResult<'a, T> = Result<T, JsonRpcError<'a>>;
+
+/// The events produced by the streaming methods `message/stream` and `tasks/resubscribe`.
+/// An `Err` item is sent to the client as an error response and ends the stream.
+pub type A2aEventStream<'a> =
+    Box<dyn Iterator<Item = HandlerResult<'a, SendStreamingMessageSuccessResponse<'a>>> + 'a>;
+
+/// Implemented by an A2A server with one method per A2A JSON-RPC method.
+/// The [JsonRpcRouter] parses the request and calls the matching method with typed params.
+///
+/// Only `message/send` and `tasks/get` are required. The other methods default to the
+/// error the A2A specification defines for an agent that does not support them.
+pub trait A2aHandler {
+    /// `message/send`
+    fn message_send<'a>(
+        &'a self,
+        params: MessageSendParams<'a>,
+    ) -> HandlerResult<'a, SendMessageResult<'a>>;
+
+    /// `message/stream`
+    fn message_stream<'a>(
+        &'a self,
+        _params: MessageSendParams<'a>,
+    ) -> HandlerResult<'a, A2aEventStream<'a>> {
+        Err(JsonRpcErrorCode::UnsupportedOperationError.into())
+    }
+
+    /// `tasks/get`
+    fn tasks_get<'a>(&'a self, params: TaskQueryParams<'a>) -> HandlerResult<'a, Task<'a>>;
+
+    /// `tasks/list`
+    fn tasks_list<'a>(
+        &'a self,
+        _params: ListTasksParams<'a>,
+    ) -> HandlerResult<'a, ListTasksResult<'a>> {
+        Err(JsonRpcErrorCode::UnsupportedOperationError.into())
+    }
+
+    /// `tasks/cancel`
+    fn tasks_cancel<'a>(&'a self, _params: CancelTaskParams<'a>) -> HandlerResult<'a, Task<'a>> {
+        Err(JsonRpcErrorCode::UnsupportedOperationError.into())
+    }
+
+    /// `tasks/resubscribe`
+    fn tasks_resubscribe<'a>(
+        &'a self,
+        _params: TaskIdParams<'a>,
+    ) -> HandlerResult<'a, A2aEventStream<'a>> {
+        Err(JsonRpcErrorCode::UnsupportedOperationError.into())
+    }
+
+    /// `tasks/pushNotificationConfig/set`
+    fn tasks_push_notification_config_set<'a>(
+        &'a self,
+        _params: TaskPushNotificationConfig<'a>,
+    ) -> HandlerResult<'a, TaskPushNotificationConfig<'a>> {
+        Err(JsonRpcErrorCode::PushNotificationNotSupportedError.into())
+    }
+
+    /// `tasks/pushNotificationConfig/get`
+    fn tasks_push_notification_config_get<'a>(
+        &'a self,
+        _params: GetTaskPushNotificationConfigParams<'a>,
+    ) -> HandlerResult<'a, TaskPushNotificationConfig<'a>> {
+        Err(JsonRpcErrorCode::PushNotificationNotSupportedError.into())
+    }
+
+    /// `tasks/pushNotificationConfig/list`
+    fn tasks_push_notification_config_list<'a>(
+        &'a self,
+        _params: ListTaskPushNotificationConfigParams<'a>,
+    ) -> HandlerResult<'a, Vec<TaskPushNotificationConfig<'a>>> {
+        Err(JsonRpcErrorCode::PushNotificationNotSupportedError.into())
+    }
+
+    /// `tasks/pushNotificationConfig/delete`
+    fn tasks_push_notification_config_delete<'a>(
+        &'a self,
+        _params: DeleteTaskPushNotificationConfigParams<'a>,
+    ) -> HandlerResult<'a, ()> {
+        Err(JsonRpcErrorCode::PushNotificationNotSupportedError.into())
+    }
+
+    /// `agent/getAuthenticatedExtendedCard`
+    fn agent_get_authenticated_extended_card<'a>(&'a self) -> HandlerResult<'a, AgentCard<'a>> {
+        Err(JsonRpcErrorCode::AuthenticatedExtendedCardNotConfiguredError.into())
+    }
+}
+
+/// The bytes to send back to the client for a request handled by the [JsonRpcRouter].
+pub enum RouterResponse<'a> {
+    /// A single JSON-RPC response object, sent with the `application/json` content type.
+    Single(Vec<u8>),
+    /// The JSON-RPC response objects of a streaming method, each sent as
+    /// the data of one server-sent event with the `text/event-stream` content type.
+    Stream(JsonRpcEventStream<'a>),
+    /// The request was a notification and the client expects no response.
+    None,
+}
+
+impl<'a> core::fmt::Debug for RouterResponse<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Single(body) => f
+                .debug_tuple("Single")
+                .field(&String::from_utf8_lossy(body))
+                .finish(),
+            Self::Stream(_) => f.write_str("Stream(..)"),
+            Self::None => f.write_str("None"),
+        }
+    }
+}
+
+/// Serializes each event of an [A2aEventStream] into a JSON-RPC response object
+/// carrying the id of the request that started the stream.
+pub struct JsonRpcEventStream<'a> {
+    id: Option<JsonRpcId>,
+    events: A2aEventStream<'a>,
+    done: bool,
+}
+
+impl<'a> JsonRpcEventStream<'a> {
+    /// The id of the request that started the stream.
+    pub fn id(&self) -> Option<&JsonRpcId> {
+        self.id.as_ref()
+    }
+}
+
+impl<'a> Iterator for JsonRpcEventStream<'a> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return Option::None;
+        }
+
+        let payload = match self.events.next()? {
+            Ok(result) => JsonRpcPayload::Success { result },
+            Err(error) => {
+                self.done = true;
+
+                JsonRpcPayload::Error { error }
+            }
+        };
+
+        Some(encode_response(self.id.clone(), payload))
+    }
+}
+
+/// Routes raw JSON-RPC request bytes to the methods of an [A2aHandler].
+///
+/// Requests are checked like [JsonRpcRequest::from_slice_strict](crate::jsonrpc::JsonRpcRequest::from_slice_strict)
+/// and the router answers with the JSON-RPC error for requests that never reach the handler:
+/// - `-32700` when the bytes are not valid JSON
+/// - `-32600` when the JSON is not a valid JSON-RPC request object
+/// - `-32601` when the method is not an A2A method
+/// - `-32602` when the params do not match the method
+#[derive(Debug, Default, Clone)]
+pub struct JsonRpcRouter<H> {
+    handler: H,
+}
+
+impl<H: A2aHandler> JsonRpcRouter<H> {
+    pub fn new(handler: H) -> Self {
+        Self { handler }
+    }
+
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    pub fn into_handler(self) -> H {
+        self.handler
+    }
+
+    /// Handle the body of an HTTP request, which is either a single request object or a batch.
+    ///
+    /// A batch is answered with an array of the responses to the requests that are not notifications,
+    /// or with [RouterResponse::None] if all of them are notifications. An empty batch is an invalid request.
+    /// Streaming methods cannot be part of a batch since their responses are server-sent events.
+    pub fn handle<'a>(&'a self, request: &'a [u8]) -> RouterResponse<'a> {
+        let raw = match parse_raw(request) {
+            Ok(raw) => raw,
+            Err(error) => return respond(error.id, error_payload(error.error)),
+        };
+
+        if !raw.get().starts_with('[') {
+            return self.handle_value(raw, false);
+        }
+
+        let batch = match serde_json::from_str::<Vec<&RawValue>>(raw.get()) {
+            Ok(batch) if !batch.is_empty() => batch,
+            _ => {
+                return respond(
+                    Some(JsonRpcId::Null),
+                    error_payload(JsonRpcErrorCode::InvalidRequest.into()),
+                )
+            }
+        };
+
+        let responses = batch
+            .into_iter()
+            .filter_map(|raw| match self.handle_value(raw, true) {
+                RouterResponse::Single(body) => Some(body),
+                _ => Option::None,
+            })
+            .collect::<Vec<Vec<u8>>>();
+
+        if responses.is_empty() {
+            return RouterResponse::None;
+        }
+
+        let mut body = vec![b'['];
+        body.extend(responses.join(&b','));
+        body.push(b']');
+
+        RouterResponse::Single(body)
+    }
+
+    fn handle_value<'a>(&'a self, raw: &'a RawValue, batched: bool) -> RouterResponse<'a> {
+        let request = match parse_request_envelope(raw) {
+            Ok(request) => request,
+            Err(error) => return respond(error.id, error_payload(error.error)),
+        };
+        let method = request.method;
+
+        if batched
+            && matches!(
+                method,
+                JsonRpcMethods::MessageStream | JsonRpcMethods::TasksResubscribe
+            )
+        {
+            return respond(
+                request.id,
+                error_payload(
+                    JsonRpcError::new(JsonRpcErrorCode::InvalidRequest).with_message(format!(
+                        "The streaming method `{method}` cannot be part of a batch"
+                    )),
+                ),
+            );
+        }
+
+        let id = request.id;
+        let params = request.params;
+        let handler = &self.handler;
+
+        match method {
+            JsonRpcMethods::MessageSend => {
+                dispatch::<MessageSend, _>(id, params, |params| handler.message_send(params))
+            }
+            JsonRpcMethods::MessageStream => {
+                stream::<MessageStream, _>(id, params, |params| handler.message_stream(params))
+            }
+            JsonRpcMethods::TasksGet => {
+                dispatch::<TasksGet, _>(id, params, |params| handler.tasks_get(params))
+            }
+            JsonRpcMethods::TasksList => {
+                dispatch::<TasksList, _>(id, params, |params| handler.tasks_list(params))
+            }
+            JsonRpcMethods::TasksCancel => {
+                dispatch::<TasksCancel, _>(id, params, |params| handler.tasks_cancel(params))
+            }
+            JsonRpcMethods::TasksResubscribe => {
+                stream::<TasksResubscribe, _>(id, params, |params| {
+                    handler.tasks_resubscribe(params)
+                })
+            }
+            JsonRpcMethods::TasksPushNotificationConfigSet => {
+                dispatch::<TasksPushNotificationConfigSet, _>(id, params, |params| {
+                    handler.tasks_push_notification_config_set(params)
+                })
+            }
+            JsonRpcMethods::TasksPushNotificationConfigGet => {
+                dispatch::<TasksPushNotificationConfigGet, _>(id, params, |params| {
+                    handler.tasks_push_notification_config_get(params)
+                })
+            }
+            JsonRpcMethods::TasksPushNotificationConfigList => {
+                dispatch::<TasksPushNotificationConfigList, _>(id, params, |params| {
+                    handler.tasks_push_notification_config_list(params)
+                })
+            }
+            JsonRpcMethods::TasksPushNotificationConfigDelete => {
+                dispatch::<TasksPushNotificationConfigDelete, _>(id, params, |params| {
+                    handler.tasks_push_notification_config_delete(params)
+                })
+            }
+            JsonRpcMethods::AgentGetAuthenticatedExtendedCard => {
+                dispatch::<AgentGetAuthenticatedExtendedCard, _>(id, params, |_| {
+                    handler.agent_get_authenticated_extended_card()
+                })
+            }
+        }
+    }
+}
+
+/// Parse the params of method `M`, defaulting to `null` when they are omitted.
+fn parse_params<'a, M>(params: Option<&'a RawValue>) -> Result<M::Params, JsonRpcErrorCode>
+where
+    M: A2aMethod<'a>,
+    M::Params: Deserialize<'a>,
+{
+    serde_json::from_str(params.map(RawValue::get).unwrap_or("null"))
+        .map_err(|_| JsonRpcErrorCode::InvalidParams)
+}
+
+fn dispatch<'a, M, F>(
+    id: Option<JsonRpcId>,
+    params: Option<&'a RawValue>,
+    call: F,
+) -> RouterResponse<'a>
+where
+    M: A2aMethod<'a>,
+    M::Params: Deserialize<'a>,
+    M::Result: Serialize,
+    F: FnOnce(M::Params) -> HandlerResult<'a, M::Result>,
+{
+    let params = match parse_params::<M>(params) {
+        Ok(params) => params,
+        Err(error) => return respond(id, error_payload(error.into())),
+    };
+
+    let payload = match call(params) {
+        Ok(result) => JsonRpcPayload::Success { result },
+        Err(error) => JsonRpcPayload::Error { error },
+    };
+
+    respond(id, payload)
+}
+
+fn stream<'a, M, F>(
+    id: Option<JsonRpcId>,
+    params: Option<&'a RawValue>,
+    call: F,
+) -> RouterResponse<'a>
+where
+    M: A2aMethod<'a, Result = SendStreamingMessageSuccessResponse<'a>>,
+    M::Params: Deserialize<'a>,
+    F: FnOnce(M::Params) -> HandlerResult<'a, A2aEventStream<'a>>,
+{
+    let params = match parse_params::<M>(params) {
+        Ok(params) => params,
+        Err(error) => return respond(id, error_payload(error.into())),
+    };
+
+    match call(params) {
+        Ok(events) => match id {
+            Some(id) => RouterResponse::Stream(JsonRpcEventStream {
+                id: Some(id),
+                events,
+                done: false,
+            }),
+            Option::None => {
+                events.for_each(drop);
+
+                RouterResponse::None
+            }
+        },
+        Err(error) => respond(id, error_payload(error)),
+    }
+}
+
+/// A request without an `id` is a notification and gets no response.
+fn respond<'a, T: Serialize>(
+    id: Option<JsonRpcId>,
+    payload: JsonRpcPayload<'_, T>,
+) -> RouterResponse<'a> {
+    match id {
+        Some(id) => RouterResponse::Single(encode_response(Some(id), payload)),
+        Option::None => RouterResponse::None,
+    }
+}
+
+fn encode_response<T: Serialize>(id: Option<JsonRpcId>, payload: JsonRpcPayload<'_, T>) -> Vec<u8> {
+    let response = JsonRpcResponse {
+        jsonrpc: "2.0",
+        id: id.clone(),
+        payload,
+    };
+
+    serde_json::to_vec(&response).unwrap_or_else(|_| {
+        let response = JsonRpcResponse {
+            jsonrpc: "2.0",
+            id,
+            payload: error_payload(JsonRpcErrorCode::InternalError.into()),
+        };
+
+        serde_json::to_vec(&response).unwrap_or_default()
+    })
+}
+
+fn error_payload(error: JsonRpcError<'_>) -> JsonRpcPayload<'_, ()> {
+    JsonRpcPayload::Error { error }
+}
@@ -0,0 +1,291 @@
+use std::{borrow::Cow, collections::BTreeMap, fmt};
+
+use serde::Deserialize;
+use serde_json::value::RawValue;
+
+use crate::{
+    impl_into_owned,
+    jsonrpc::{
+        JsonRpcError, JsonRpcId, JsonRpcMethods, JsonRpcPayload, JsonRpcRequest, JsonRpcResponse,
+    },
+    JsonRpcErrorCode,
+};
+
+/// The only JSON-RPC version accepted in the `jsonrpc` member.
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// A JSON-RPC request or response object that breaks the JSON-RPC 2.0 envelope rules,
+/// together with the id the error response has to be sent with.
+///
+/// Malformed requests are reported with the codes a server answers them with:
+/// - [JsonRpcErrorCode::ParseError] when the bytes are not valid JSON
+/// - [JsonRpcErrorCode::InvalidRequest] when the JSON is not a valid request object
+/// - [JsonRpcErrorCode::MethodNotFound] when the method is not an A2A method
+/// - [JsonRpcErrorCode::InvalidParams] when the params cannot be parsed
+///
+/// Malformed responses are reported with [JsonRpcErrorCode::ParseError] or
+/// [JsonRpcErrorCode::InvalidAgentResponseError].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct JsonRpcEnvelopeError<'a> {
+    /// `Some(JsonRpcId::Null)` when the id could not be determined and
+    /// `None` when the offending request is a notification, which must not be answered.
+    pub id: Option<JsonRpcId>,
+    /// The error to answer with. The message describes the rule that was broken.
+    pub error: JsonRpcError<'a>,
+}
+
+impl<'a> JsonRpcEnvelopeError<'a> {
+    fn new(
+        id: Option<JsonRpcId>,
+        code: JsonRpcErrorCode,
+        message: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        Self {
+            id,
+            error: JsonRpcError::new(code).with_message(message),
+        }
+    }
+
+    pub fn code(&self) -> JsonRpcErrorCode {
+        self.error.code
+    }
+
+    /// The error response to send back, or `None` if the request was a notification.
+    pub fn into_response(self) -> Option<JsonRpcResponse<'a, ()>> {
+        Some(JsonRpcResponse {
+            jsonrpc: JSONRPC_VERSION,
+            id: Some(self.id?),
+            payload: JsonRpcPayload::Error { error: self.error },
+        })
+    }
+}
+
+impl<'a> From<JsonRpcEnvelopeError<'a>> for JsonRpcErrorCode {
+    fn from(value: JsonRpcEnvelopeError<'a>) -> Self {
+        value.code()
+    }
+}
+
+impl<'a> fmt::Display for JsonRpcEnvelopeError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<'a> std::error::Error for JsonRpcEnvelopeError<'a> {}
+
+impl<'a, T: Deserialize<'a>> JsonRpcRequest<'a, T> {
+    /// Parse a request object, enforcing the JSON-RPC 2.0 envelope rules:
+    /// - `jsonrpc` is exactly `"2.0"`
+    /// - `method` is a string naming an A2A method
+    /// - `id`, if present, is a string, an integer or `null`
+    /// - `params`, if present, is an object or an array
+    pub fn from_slice_strict(bytes: &'a [u8]) -> Result<Self, JsonRpcEnvelopeError<'a>> {
+        let raw = parse_raw(bytes)?;
+        let envelope = parse_request_envelope(raw)?;
+
+        let params = match envelope.params {
+            Some(params) => Some(serde_json::from_str::<T>(params.get()).map_err(|error| {
+                JsonRpcEnvelopeError::new(
+                    envelope.id.clone(),
+                    JsonRpcErrorCode::InvalidParams,
+                    format!("Invalid params for `{}`: {error}", envelope.method),
+                )
+            })?),
+            None => None,
+        };
+
+        Ok(Self {
+            jsonrpc: JSONRPC_VERSION,
+            method: envelope.method,
+            params,
+            id: envelope.id,
+        })
+    }
+}
+
+impl<'a, T: Deserialize<'a>> JsonRpcResponse<'a, T> {
+    /// Parse a response object, enforcing the JSON-RPC 2.0 envelope rules:
+    /// - `jsonrpc` is exactly `"2.0"`
+    /// - `id` is present and is a string, an integer or `null`
+    /// - exactly one of `result` and `error` is present
+    /// - `id` is `null` when the error is a parse error
+    pub fn from_slice_strict(bytes: &'a [u8]) -> Result<Self, JsonRpcEnvelopeError<'a>> {
+        let members = parse_members(
+            parse_raw(bytes)?,
+            JsonRpcErrorCode::InvalidAgentResponseError,
+        )?;
+
+        let invalid = |id: &JsonRpcId, message: String| {
+            JsonRpcEnvelopeError::new(
+                Some(id.clone()),
+                JsonRpcErrorCode::InvalidAgentResponseError,
+                message,
+            )
+        };
+
+        let id = match members.get("id") {
+            Some(id) => parse_id(id, JsonRpcErrorCode::InvalidAgentResponseError)?,
+            None => {
+                return Err(invalid(
+                    &JsonRpcId::Null,
+                    "The response has no `id` member".into(),
+                ))
+            }
+        };
+
+        check_version(&members, &id, JsonRpcErrorCode::InvalidAgentResponseError)?;
+
+        let payload = match (members.get("result"), members.get("error")) {
+            (Some(result), None) => JsonRpcPayload::Success {
+                result: serde_json::from_str::<T>(result.get())
+                    .map_err(|error| invalid(&id, format!("Invalid `result` member: {error}")))?,
+            },
+            (None, Some(error)) => {
+                let error = serde_json::from_str::<JsonRpcError>(error.get())
+                    .map_err(|error| invalid(&id, format!("Invalid `error` member: {error}")))?;
+
+                if error.code == JsonRpcErrorCode::ParseError && id != JsonRpcId::Null {
+                    return Err(invalid(
+                        &id,
+                        "The `id` of a parse error response must be null".into(),
+                    ));
+                }
+
+                JsonRpcPayload::Error { error }
+            }
+            (Some(_), Some(_)) => {
+                return Err(invalid(
+                    &id,
+                    "The response has both a `result` and an `error` member".into(),
+                ))
+            }
+            (None, None) => {
+                return Err(invalid(
+                    &id,
+                    "The response has neither a `result` nor an `error` member".into(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            jsonrpc: JSONRPC_VERSION,
+            id: Some(id),
+            payload,
+        })
+    }
+}
+
+/// A request object whose envelope is valid and whose method is resolved, with the params not yet parsed.
+pub(crate) struct RequestEnvelope<'a> {
+    pub(crate) method: JsonRpcMethods,
+    pub(crate) params: Option<&'a RawValue>,
+    pub(crate) id: Option<JsonRpcId>,
+}
+
+pub(crate) fn parse_raw(bytes: &[u8]) -> Result<&RawValue, JsonRpcEnvelopeError<'static>> {
+    serde_json::from_slice::<&RawValue>(bytes).map_err(|error| {
+        JsonRpcEnvelopeError::new(
+            Some(JsonRpcId::Null),
+            JsonRpcErrorCode::ParseError,
+            format!("{}: {error}", JsonRpcErrorCode::ParseError.description()),
+        )
+    })
+}
+
+pub(crate) fn parse_request_envelope(
+    raw: &RawValue,
+) -> Result<RequestEnvelope<'_>, JsonRpcEnvelopeError<'static>> {
+    let members = parse_members(raw, JsonRpcErrorCode::InvalidRequest)?;
+
+    let id = match members.get("id") {
+        Some(id) => Some(parse_id(id, JsonRpcErrorCode::InvalidRequest)?),
+        None => None,
+    };
+
+    // Only a valid request without an `id` is a notification, an invalid one is answered.
+    let invalid = |message: &str| {
+        JsonRpcEnvelopeError::new(
+            Some(id.clone().unwrap_or(JsonRpcId::Null)),
+            JsonRpcErrorCode::InvalidRequest,
+            message.to_owned(),
+        )
+    };
+
+    if !is_version(&members) {
+        return Err(invalid("The `jsonrpc` member must be exactly \"2.0\""));
+    }
+
+    let method = members
+        .get("method")
+        .and_then(|method| serde_json::from_str::<Cow<str>>(method.get()).ok())
+        .ok_or_else(|| invalid("The `method` member must be a string"))?;
+
+    let method =
+        JsonRpcMethods::try_from(method.as_ref()).map_err(|error| JsonRpcEnvelopeError {
+            id: id.clone(),
+            error: JsonRpcError {
+                code: error.code,
+                message: Cow::Owned(error.message.into_owned()),
+                data: None,
+            },
+        })?;
+
+    let params = members.get("params").copied();
+    if params.is_some_and(|params| !params.get().starts_with(['{', '['])) {
+        return Err(invalid("The `params` member must be an object or an array"));
+    }
+
+    Ok(RequestEnvelope { method, params, id })
+}
+
+fn parse_members(
+    raw: &RawValue,
+    code: JsonRpcErrorCode,
+) -> Result<BTreeMap<Cow<'_, str>, &RawValue>, JsonRpcEnvelopeError<'static>> {
+    serde_json::from_str::<BTreeMap<Cow<str>, &RawValue>>(raw.get()).map_err(|_| {
+        JsonRpcEnvelopeError::new(
+            Some(JsonRpcId::Null),
+            code,
+            "A JSON-RPC message must be a JSON object",
+        )
+    })
+}
+
+fn parse_id(
+    raw: &RawValue,
+    code: JsonRpcErrorCode,
+) -> Result<JsonRpcId, JsonRpcEnvelopeError<'static>> {
+    serde_json::from_str::<JsonRpcId>(raw.get()).map_err(|_| {
+        JsonRpcEnvelopeError::new(
+            Some(JsonRpcId::Null),
+            code,
+            "The `id` member must be a string, an integer or null",
+        )
+    })
+}
+
+fn is_version(members: &BTreeMap<Cow<'_, str>, &RawValue>) -> bool {
+    members
+        .get("jsonrpc")
+        .and_then(|version| serde_json::from_str::<Cow<str>>(version.get()).ok())
+        .is_some_and(|version| version == JSONRPC_VERSION)
+}
+
+fn check_version(
+    members: &BTreeMap<Cow<'_, str>, &RawValue>,
+    id: &JsonRpcId,
+    code: JsonRpcErrorCode,
+) -> Result<(), JsonRpcEnvelopeError<'static>> {
+    if is_version(members) {
+        Ok(())
+    } else {
+        Err(JsonRpcEnvelopeError::new(
+            Some(id.clone()),
+            code,
+            "The `jsonrpc` member must be exactly \"2.0\"",
+        ))
+    }
+}
+
+impl_into_owned!(JsonRpcEnvelopeError { id, error });
@@ -7,6 +7,12 @@ pub use protocol_data::*;
 mod util_types;
 pub use util_types::*;
 
+mod owned;
+pub use owned::*;
+
+mod canonical;
+pub use canonical::*;
+
 pub mod jsonrpc;
 
 mod errors;
@@ -0,0 +1,132 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{
+    jsonrpc::{JsonRpcId, JsonRpcMethods},
+    ArtifactUpdateKind, JsonNumber, JsonRpcErrorCode, MessageKind, MessageRole, OAuthFlow,
+    OAuthFlows, StatusUpdateKind, TaskKind, TaskState, TransportProtocol,
+};
+
+/// Converts a value that borrows from its input into one that owns all of its data.
+///
+/// Deserialized protocol types borrow their strings from the input buffer where they can.
+/// `into_owned()` copies those strings so that the value can outlive the buffer, e.g. to keep
+/// a [crate::Task] in a store, move it into a spawned task or send it over a channel.
+///
+/// ```
+/// use agentic_types::{IntoOwned, Message};
+///
+/// fn parse(body: Vec<u8>) -> Message<'static> {
+///     serde_json::from_slice::<Message>(&body).unwrap().into_owned()
+/// }
+///
+/// let message = parse(br#"{"role": "user", "parts": [], "messageId": "m-1", "kind": "message"}"#.to_vec());
+/// assert_eq!(message.message_id, "m-1");
+/// ```
+pub trait IntoOwned {
+    /// The same type with every borrow replaced by owned data.
+    type Owned: 'static;
+
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl<'a> IntoOwned for Cow<'a, str> {
+    type Owned = Cow<'static, str>;
+
+    fn into_owned(self) -> Cow<'static, str> {
+        Cow::Owned(Cow::into_owned(self))
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(T::into_owned)
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(T::into_owned).collect()
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Box<T> {
+    type Owned = Box<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        Box::new((*self).into_owned())
+    }
+}
+
+impl<K, V> IntoOwned for BTreeMap<K, V>
+where
+    K: IntoOwned,
+    K::Owned: Ord,
+    V: IntoOwned,
+{
+    type Owned = BTreeMap<K::Owned, V::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter()
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect()
+    }
+}
+
+/// Types that never borrow are their own owned counterpart.
+macro_rules! impl_into_owned_identity {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl IntoOwned for $ty {
+                type Owned = Self;
+
+                fn into_owned(self) -> Self::Owned {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_into_owned_identity!(
+    (),
+    bool,
+    i16,
+    i64,
+    u64,
+    String,
+    JsonNumber,
+    JsonRpcErrorCode,
+    JsonRpcId,
+    JsonRpcMethods,
+    ArtifactUpdateKind,
+    MessageKind,
+    StatusUpdateKind,
+    TaskKind,
+    MessageRole,
+    OAuthFlow,
+    OAuthFlows,
+    TaskState,
+    TransportProtocol,
+);
+
+/// Implements [IntoOwned] for a struct with a single `'a` lifetime by converting each of its fields.
+/// Every field has to be listed, so a field added later cannot be forgotten.
+macro_rules! impl_into_owned {
+    ($name:ident { $($field:ident),* $(,)? }) => {
+        impl<'a> $crate::IntoOwned for $name<'a> {
+            type Owned = $name<'static>;
+
+            fn into_owned(self) -> Self::Owned {
+                $name {
+                    $($field: $crate::IntoOwned::into_owned(self.$field),)*
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use impl_into_owned;
@@ -1,6 +1,8 @@
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
-use crate::{JsonStrMemKV, Part};
+use crate::{impl_into_owned, JsonStrMemKV, MissingFieldError, Part};
 
 /// Represents a tangible output generated by the agent during a task (a file, data structure, or other resource generated).
 /// Artifacts are the results or products of the agent's work.
@@ -9,15 +11,15 @@ use crate::{JsonStrMemKV, Part};
 pub struct Artifact<'a> {
     /// A unique identifier (e.g. UUID) for the artifact within the scope of the task.
     #[serde(borrow)]
-    artifact_id: &'a str,
+    artifact_id: Cow<'a, str>,
     /// An optional, human-readable name for the artifact.
     #[serde(borrow)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<&'a str>,
+    name: Option<Cow<'a, str>>,
     /// An optional, human-readable description of the artifact.
     #[serde(borrow)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    description: Option<&'a str>,
+    description: Option<Cow<'a, str>>,
     ///  An array of content parts that make up the artifact.
     #[serde(borrow)]
     parts: Vec<Part<'a>>,
@@ -28,5 +30,118 @@ pub struct Artifact<'a> {
     /// The URIs of extensions that are relevant to this artifact.
     #[serde(borrow)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    extensions: Option<Vec<&'a str>>,
+    extensions: Option<Vec<Cow<'a, str>>>,
+}
+
+impl<'a> Artifact<'a> {
+    /// Start building an artifact. `artifactId` and at least one part are required.
+    pub fn builder() -> ArtifactBuilder<'a> {
+        ArtifactBuilder::default()
+    }
+
+    pub fn artifact_id(&self) -> &str {
+        &self.artifact_id
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn parts(&self) -> &[Part<'a>] {
+        self.parts.as_slice()
+    }
+
+    pub fn metadata(&self) -> Option<&JsonStrMemKV<'a>> {
+        self.metadata.as_ref()
+    }
+
+    pub fn extensions(&self) -> Option<&[Cow<'a, str>]> {
+        self.extensions.as_deref()
+    }
+
+    pub(crate) fn parts_mut(&mut self) -> &mut Vec<Part<'a>> {
+        &mut self.parts
+    }
+
+    /// An artifact with the same id and none of the other members, e.g. for an appended chunk.
+    pub(crate) fn without_details(&self) -> Self {
+        Self {
+            artifact_id: self.artifact_id.clone(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Builds an [Artifact], checking at [ArtifactBuilder::build] that the required fields are set.
+#[derive(Debug, Default, Clone)]
+pub struct ArtifactBuilder<'a> {
+    artifact: Artifact<'a>,
+}
+
+impl<'a> ArtifactBuilder<'a> {
+    pub fn artifact_id(mut self, artifact_id: impl Into<Cow<'a, str>>) -> Self {
+        self.artifact.artifact_id = artifact_id.into();
+
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.artifact.name = Some(name.into());
+
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<Cow<'a, str>>) -> Self {
+        self.artifact.description = Some(description.into());
+
+        self
+    }
+
+    pub fn part(mut self, part: Part<'a>) -> Self {
+        self.artifact.parts.push(part);
+
+        self
+    }
+
+    pub fn metadata(mut self, metadata: JsonStrMemKV<'a>) -> Self {
+        self.artifact.metadata = Some(metadata);
+
+        self
+    }
+
+    pub fn extension(mut self, uri: impl Into<Cow<'a, str>>) -> Self {
+        self.artifact
+            .extensions
+            .get_or_insert_with(Vec::new)
+            .push(uri.into());
+
+        self
+    }
+
+    pub fn build(self) -> Result<Artifact<'a>, MissingFieldError> {
+        let artifact = self.artifact;
+
+        MissingFieldError::check(
+            "Artifact",
+            [
+                ("artifactId", !artifact.artifact_id.is_empty()),
+                ("parts", !artifact.parts.is_empty()),
+            ],
+        )?;
+
+        Ok(artifact)
+    }
 }
+
+impl_into_owned!(Artifact {
+    artifact_id,
+    name,
+    description,
+    parts,
+    metadata,
+    extensions,
+});
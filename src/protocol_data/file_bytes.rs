@@ -0,0 +1,194 @@
+use std::io;
+
+use crate::{FileBytesError, FileWithBytes};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The number of base64 characters decoded at a time by [FileDecoder::decode_to].
+const BLOCK_SIZE: usize = 4 * 1024;
+
+/// Decodes the base64 content of a [FileWithBytes], rejecting payloads larger than `max_size` bytes.
+///
+/// The size of the decoded content is computed from the length of the base64 text,
+/// so an oversized payload is rejected before anything is decoded.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct FileDecoder {
+    max_size: Option<usize>,
+}
+
+impl FileDecoder {
+    /// A decoder without a size limit.
+    pub fn new() -> Self {
+        Self { max_size: None }
+    }
+
+    /// Reject files whose decoded content is larger than `max_size` bytes.
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+
+        self
+    }
+
+    pub fn max_size(&self) -> Option<usize> {
+        self.max_size
+    }
+
+    pub fn decode(&self, file: &FileWithBytes<'_>) -> Result<Vec<u8>, FileBytesError> {
+        self.decode_base64(file.bytes())
+    }
+
+    /// Decode base64 text that is not part of a file, e.g. the content of a `data:` URI.
+    pub fn decode_base64(&self, encoded: &str) -> Result<Vec<u8>, FileBytesError> {
+        let mut decoded = Vec::with_capacity(self.check_size(encoded)?);
+        self.decode_base64_to(encoded, &mut decoded)?;
+
+        Ok(decoded)
+    }
+
+    /// Decode into `writer` one block at a time and return the number of bytes written.
+    /// When the base64 text is invalid, the blocks before the invalid one have already been written.
+    pub fn decode_to<W: io::Write>(
+        &self,
+        file: &FileWithBytes<'_>,
+        writer: W,
+    ) -> Result<u64, FileBytesError> {
+        self.decode_base64_to(file.bytes(), writer)
+    }
+
+    pub fn decode_base64_to<W: io::Write>(
+        &self,
+        encoded: &str,
+        mut writer: W,
+    ) -> Result<u64, FileBytesError> {
+        self.check_size(encoded)?;
+        let encoded = encoded.as_bytes();
+
+        let mut buffer = Vec::with_capacity(BLOCK_SIZE / 4 * 3);
+        let mut written = 0u64;
+
+        let last = encoded.len().saturating_sub(1) / BLOCK_SIZE;
+
+        for (index, block) in encoded.chunks(BLOCK_SIZE).enumerate() {
+            buffer.clear();
+            decode_block(block, index * BLOCK_SIZE, index == last, &mut buffer)?;
+
+            writer.write_all(&buffer)?;
+            written += buffer.len() as u64;
+        }
+
+        Ok(written)
+    }
+
+    fn check_size(&self, encoded: &str) -> Result<usize, FileBytesError> {
+        let size = decoded_len(encoded);
+
+        match self.max_size {
+            Some(max_size) if size > max_size => Err(FileBytesError::TooLarge { size, max_size }),
+            _ => Ok(size),
+        }
+    }
+}
+
+impl Default for FileDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Standard base64 with padding.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let group = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or_default(),
+            chunk.get(2).copied().unwrap_or_default(),
+        ];
+        let sextets = [
+            group[0] >> 2,
+            (group[0] & 0b11) << 4 | group[1] >> 4,
+            (group[1] & 0b1111) << 2 | group[2] >> 6,
+            group[2] & 0b11_1111,
+        ];
+
+        for (index, sextet) in sextets.into_iter().enumerate() {
+            match index <= chunk.len() {
+                true => encoded.push(ALPHABET[sextet as usize] as char),
+                false => encoded.push('='),
+            }
+        }
+    }
+
+    encoded
+}
+
+/// The number of bytes `encoded` decodes to, assuming it is valid.
+fn decoded_len(encoded: &str) -> usize {
+    let unpadded = encoded.trim_end_matches('=').len();
+
+    unpadded / 4 * 3 + (unpadded % 4).saturating_sub(1)
+}
+
+/// Decode a block of base64 text whose length is a multiple of 4 unless it is the `last` block,
+/// the only one that may end with padding.
+/// `offset` is the position of the block in the whole text, for error messages.
+fn decode_block(
+    block: &[u8],
+    offset: usize,
+    last: bool,
+    decoded: &mut Vec<u8>,
+) -> Result<(), FileBytesError> {
+    let unpadded = match block.iter().position(|byte| *byte == b'=') {
+        Some(padding) => {
+            let is_padding = block[padding..].iter().all(|byte| *byte == b'=');
+
+            if !last || !is_padding || !block.len().is_multiple_of(4) || block.len() - padding > 2 {
+                return Err(FileBytesError::InvalidBase64 {
+                    offset: offset + padding,
+                });
+            }
+
+            &block[..padding]
+        }
+        None => block,
+    };
+
+    for (index, group) in unpadded.chunks(4).enumerate() {
+        let mut sextets = [0u8; 4];
+
+        for (position, byte) in group.iter().enumerate() {
+            sextets[position] = sextet(*byte).ok_or(FileBytesError::InvalidBase64 {
+                offset: offset + index * 4 + position,
+            })?;
+        }
+
+        let bytes = [
+            sextets[0] << 2 | sextets[1] >> 4,
+            sextets[1] << 4 | sextets[2] >> 2,
+            sextets[2] << 6 | sextets[3],
+        ];
+
+        match group.len() {
+            1 => {
+                return Err(FileBytesError::InvalidBase64 {
+                    offset: offset + index * 4,
+                })
+            }
+            length => decoded.extend_from_slice(&bytes[..length - 1]),
+        }
+    }
+
+    Ok(())
+}
+
+fn sextet(byte: u8) -> Option<u8> {
+    match byte {
+        b'A'..=b'Z' => Some(byte - b'A'),
+        b'a'..=b'z' => Some(byte - b'a' + 26),
+        b'0'..=b'9' => Some(byte - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
@@ -0,0 +1,270 @@
+use std::{
+    borrow::Cow,
+    fs,
+    io::Read,
+    path::{self, Component, Path, PathBuf},
+};
+
+use crate::{FileBytesError, FileDecoder, FileFetchError, MediaType};
+
+/// Fetches the content of a file referenced by the `uri` of a [crate::FileWithUri],
+/// see [crate::FileWithUri::resolve].
+///
+/// Implemented for an HTTP client by the application, [LocalFileFetcher] handles
+/// `data:` and `file:` URIs.
+pub trait FileFetcher {
+    fn fetch(&self, uri: &str) -> Result<FetchedFile, FileFetchError>;
+}
+
+/// The content of a fetched file.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct FetchedFile {
+    pub content: Vec<u8>,
+    /// The media type reported by the source, e.g. the `Content-Type` of an HTTP response.
+    pub mime_type: Option<MediaType<'static>>,
+}
+
+/// Fetches `data:` URIs and, when a root directory is set, `file:` URIs of files inside it.
+///
+/// Files are only read below the root, after resolving symbolic links and `..`,
+/// since the uri comes from the other agent.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Hash)]
+pub struct LocalFileFetcher {
+    root: Option<PathBuf>,
+    max_size: Option<usize>,
+}
+
+impl LocalFileFetcher {
+    /// A fetcher for `data:` URIs only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also serve `file:` URIs of the files below `root`.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+
+        self
+    }
+
+    /// Reject files larger than `max_size` bytes before reading or decoding them.
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+
+        self
+    }
+
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    pub fn max_size(&self) -> Option<usize> {
+        self.max_size
+    }
+
+    /// `data:[<media type>][;base64],<data>` as defined by RFC 2397.
+    fn fetch_data(&self, uri: &str, data: &str) -> Result<FetchedFile, FileFetchError> {
+        let (header, content) = data
+            .split_once(',')
+            .ok_or_else(|| FileFetchError::InvalidUri {
+                uri: uri.to_owned(),
+                reason: "a data URI must have a `,` before its content",
+            })?;
+        let (media_type, base64) = match header.strip_suffix(";base64") {
+            Some(media_type) => (media_type, true),
+            None => (header, false),
+        };
+
+        let mime_type = match media_type.is_empty() {
+            true => None,
+            false => Some(
+                MediaType::parse(percent_decode_str(uri, media_type)?.into_owned()).map_err(
+                    |error| FileFetchError::InvalidUri {
+                        uri: uri.to_owned(),
+                        reason: error.reason,
+                    },
+                )?,
+            ),
+        };
+
+        let content = match base64 {
+            true => self.decoder().decode_base64(content)?,
+            false => {
+                let content = percent_decode(uri, content)?.into_owned();
+                self.check_size(content.len())?;
+
+                content
+            }
+        };
+
+        Ok(FetchedFile {
+            content,
+            mime_type: mime_type.or(Some(MediaType::TEXT_PLAIN)),
+        })
+    }
+
+    /// `file:///path` or `file://localhost/path`.
+    fn fetch_file(&self, uri: &str, path: &str) -> Result<FetchedFile, FileFetchError> {
+        let root = self
+            .root
+            .as_ref()
+            .ok_or_else(|| FileFetchError::UnsupportedScheme(uri.to_owned()))?;
+        let path = path
+            .strip_prefix("//")
+            .and_then(|path| path.strip_prefix("localhost").or(Some(path)))
+            .filter(|path| path.starts_with('/'))
+            .ok_or_else(|| FileFetchError::InvalidUri {
+                uri: uri.to_owned(),
+                reason: "a file URI must have an absolute path on the local host",
+            })?;
+
+        let not_allowed = || FileFetchError::NotAllowed(uri.to_owned());
+
+        // The path is checked before touching the file system, so that the errors
+        // do not tell which files exist outside of the root.
+        let path = normalize(Path::new(percent_decode_str(uri, path)?.as_ref()));
+        let real_root = fs::canonicalize(root)?;
+        if !path.starts_with(normalize(&path::absolute(root)?)) && !path.starts_with(&real_root) {
+            return Err(not_allowed());
+        }
+
+        // A symbolic link below the root can point outside of it, or be swapped after the check,
+        // so the file that was opened has to be the one at the real path.
+        let file = fs::File::open(&path)?;
+        let real_path = fs::canonicalize(&path)?;
+        if !real_path.starts_with(&real_root)
+            || !same_file(&file.metadata()?, &fs::metadata(&real_path)?)
+        {
+            return Err(not_allowed());
+        }
+        let size = usize::try_from(file.metadata()?.len()).unwrap_or(usize::MAX);
+        self.check_size(size)?;
+
+        let mut content = Vec::with_capacity(size);
+        let limit = self
+            .max_size
+            .map_or(u64::MAX, |max_size| max_size as u64 + 1);
+        file.take(limit).read_to_end(&mut content)?;
+        self.check_size(content.len())?;
+
+        Ok(FetchedFile {
+            content,
+            mime_type: None,
+        })
+    }
+
+    fn decoder(&self) -> FileDecoder {
+        match self.max_size {
+            Some(max_size) => FileDecoder::new().with_max_size(max_size),
+            None => FileDecoder::new(),
+        }
+    }
+
+    fn check_size(&self, size: usize) -> Result<(), FileFetchError> {
+        match self.max_size {
+            Some(max_size) if size > max_size => {
+                Err(FileBytesError::TooLarge { size, max_size }.into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FileFetcher for LocalFileFetcher {
+    fn fetch(&self, uri: &str) -> Result<FetchedFile, FileFetchError> {
+        let (scheme, rest) = uri
+            .split_once(':')
+            .ok_or_else(|| FileFetchError::InvalidUri {
+                uri: uri.to_owned(),
+                reason: "the scheme is missing",
+            })?;
+
+        if scheme.eq_ignore_ascii_case("data") {
+            self.fetch_data(uri, rest)
+        } else if scheme.eq_ignore_ascii_case("file") {
+            self.fetch_file(uri, rest)
+        } else {
+            Err(FileFetchError::UnsupportedScheme(uri.to_owned()))
+        }
+    }
+}
+
+/// Resolve `.` and `..` without following symbolic links. `..` at the root stays at the root.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+#[cfg(unix)]
+fn same_file(left: &fs::Metadata, right: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    left.dev() == right.dev() && left.ino() == right.ino()
+}
+
+/// Without file ids, the files are compared by their type, size and modification time.
+#[cfg(not(unix))]
+fn same_file(left: &fs::Metadata, right: &fs::Metadata) -> bool {
+    left.file_type() == right.file_type()
+        && left.len() == right.len()
+        && left.modified().ok() == right.modified().ok()
+}
+
+/// Decode the `%XX` escapes of `value`, which must decode to UTF-8.
+fn percent_decode_str<'v>(uri: &str, value: &'v str) -> Result<Cow<'v, str>, FileFetchError> {
+    match percent_decode(uri, value)? {
+        Cow::Borrowed(_) => Ok(value.into()),
+        Cow::Owned(decoded) => {
+            String::from_utf8(decoded)
+                .map(Into::into)
+                .map_err(|_| FileFetchError::InvalidUri {
+                    uri: uri.to_owned(),
+                    reason: "an escaped value is not UTF-8",
+                })
+        }
+    }
+}
+
+/// Decode the `%XX` escapes of `value` to any octets.
+fn percent_decode<'v>(uri: &str, value: &'v str) -> Result<Cow<'v, [u8]>, FileFetchError> {
+    if !value.contains('%') {
+        return Ok(value.as_bytes().into());
+    }
+
+    let invalid = |reason| FileFetchError::InvalidUri {
+        uri: uri.to_owned(),
+        reason,
+    };
+
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut bytes = value.bytes();
+
+    while let Some(byte) = bytes.next() {
+        if byte != b'%' {
+            decoded.push(byte);
+            continue;
+        }
+
+        let escape = [bytes.next(), bytes.next()];
+        let hex = escape
+            .into_iter()
+            .map(|digit| digit.and_then(|digit| (digit as char).to_digit(16)))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| invalid("a `%` escape must be followed by two hex digits"))?;
+
+        decoded.push((hex[0] * 16 + hex[1]) as u8);
+    }
+
+    Ok(decoded.into())
+}
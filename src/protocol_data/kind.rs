@@ -0,0 +1,84 @@
+use std::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Declares a `kind` discriminator that always serializes as its literal
+/// and refuses to deserialize from any other string.
+macro_rules! kind_discriminator {
+    ($(#[$doc:meta])* $name:ident => $value:literal) => {
+        $(#[$doc])*
+        #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+        pub struct $name;
+
+        impl $name {
+            pub const VALUE: &'static str = $value;
+
+            pub fn as_str(&self) -> &'static str {
+                Self::VALUE
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(Self::VALUE)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(Self::VALUE)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_str(KindVisitor($value)).map(|_| Self)
+            }
+        }
+    };
+}
+
+kind_discriminator!(
+    /// The `kind` of a [crate::Message], always `"message"`.
+    MessageKind => "message"
+);
+kind_discriminator!(
+    /// The `kind` of a [crate::Task], always `"task"`.
+    TaskKind => "task"
+);
+kind_discriminator!(
+    /// The `kind` of a [crate::jsonrpc::TaskStatusUpdateEvent], always `"status-update"`.
+    StatusUpdateKind => "status-update"
+);
+kind_discriminator!(
+    /// The `kind` of a [crate::jsonrpc::TaskArtifactUpdateEvent], always `"artifact-update"`.
+    ArtifactUpdateKind => "artifact-update"
+);
+
+/// Accepts exactly one string.
+struct KindVisitor(&'static str);
+
+impl<'de> de::Visitor<'de> for KindVisitor {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "the kind \"{}\"", self.0)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if value == self.0 {
+            Ok(())
+        } else {
+            Err(E::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+}
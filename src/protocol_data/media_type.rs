@@ -0,0 +1,452 @@
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{IntoOwned, JsonKey, JsonRpcError, MediaTypeError};
+
+/// A media type (MIME type) such as `text/plain`, `image/*` or `text/html; charset=utf-8`,
+/// used for the input and output modes of agents and skills and the `mimeType` of files.
+///
+/// The text is kept as written. Equality, ordering and hashing compare the type, the subtype and
+/// the parameters in order, ignoring whitespace, quotes and the ASCII case of everything but
+/// parameter values. Only the value of `charset` is case-insensitive.
+///
+/// Deserializing accepts any string, since cards in the wild declare modes such as `text`.
+/// Such an invalid media type only matches itself, [MediaType::validate] tells why it is invalid.
+#[derive(Debug, Clone)]
+pub struct MediaType<'a> {
+    raw: Cow<'a, str>,
+    /// The index of the `/` between the type and the subtype, the end of `raw` when it is invalid.
+    slash: usize,
+    /// The end of the subtype, where the parameters start.
+    essence_end: usize,
+    /// Why `raw` is not a valid media type.
+    invalid: Option<&'static str>,
+}
+
+impl<'a> MediaType<'a> {
+    pub const ANY: MediaType<'static> = MediaType::known("*/*", 1);
+    pub const TEXT_PLAIN: MediaType<'static> = MediaType::known("text/plain", 4);
+    pub const TEXT_MARKDOWN: MediaType<'static> = MediaType::known("text/markdown", 4);
+    pub const APPLICATION_JSON: MediaType<'static> = MediaType::known("application/json", 11);
+    pub const APPLICATION_OCTET_STREAM: MediaType<'static> =
+        MediaType::known("application/octet-stream", 11);
+    pub const APPLICATION_PDF: MediaType<'static> = MediaType::known("application/pdf", 11);
+    pub const IMAGE_PNG: MediaType<'static> = MediaType::known("image/png", 5);
+    pub const IMAGE_JPEG: MediaType<'static> = MediaType::known("image/jpeg", 5);
+
+    const fn known(raw: &'static str, slash: usize) -> MediaType<'static> {
+        MediaType {
+            raw: Cow::Borrowed(raw),
+            slash,
+            essence_end: raw.len(),
+            invalid: None,
+        }
+    }
+
+    /// Parse `type "/" subtype *( ";" parameter )` as defined by RFC 9110.
+    /// Surrounding whitespace is removed, `*/*` and `type/*` are accepted as wildcards.
+    pub fn parse(media_type: impl Into<Cow<'a, str>>) -> Result<Self, MediaTypeError> {
+        let media_type = Self::lenient(media_type);
+        media_type.validate()?;
+
+        Ok(media_type)
+    }
+
+    /// Parse `media_type` like [MediaType::parse], keeping it as an invalid media type when it does not parse.
+    pub fn lenient(media_type: impl Into<Cow<'a, str>>) -> Self {
+        let raw = match media_type.into() {
+            Cow::Borrowed(media_type) => Cow::Borrowed(media_type.trim()),
+            Cow::Owned(media_type) if media_type.trim().len() == media_type.len() => {
+                Cow::Owned(media_type)
+            }
+            Cow::Owned(media_type) => Cow::Owned(media_type.trim().to_owned()),
+        };
+
+        match split(&raw) {
+            Ok((slash, essence_end)) => Self {
+                raw,
+                slash,
+                essence_end,
+                invalid: None,
+            },
+            Err(reason) => Self {
+                slash: raw.len(),
+                essence_end: raw.len(),
+                raw,
+                invalid: Some(reason),
+            },
+        }
+    }
+
+    /// Fails for a media type that was deserialized or made with [MediaType::lenient] and does not parse.
+    pub fn validate(&self) -> Result<(), MediaTypeError> {
+        match self.invalid {
+            Some(reason) => Err(MediaTypeError {
+                media_type: self.raw.to_string(),
+                reason,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.invalid.is_none()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// The type without the subtype, e.g. `image`. The whole text of an invalid media type.
+    pub fn main_type(&self) -> &str {
+        &self.raw[..self.slash]
+    }
+
+    /// Empty for an invalid media type.
+    pub fn subtype(&self) -> &str {
+        self.raw
+            .get(self.slash + 1..self.essence_end)
+            .unwrap_or_default()
+    }
+
+    /// The type and subtype without the parameters, e.g. `text/html`.
+    pub fn essence(&self) -> &str {
+        &self.raw[..self.essence_end]
+    }
+
+    /// The parameters in order, with the quotes of quoted values removed.
+    pub fn parameters(&self) -> impl Iterator<Item = (&str, &str)> {
+        parse_parameters(&self.raw[self.essence_end..])
+            .unwrap_or_default()
+            .into_iter()
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters()
+            .find(|(parameter, _)| parameter.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// The subtype is `*`, as in `image/*` or `*/*`.
+    pub fn is_wildcard(&self) -> bool {
+        self.subtype() == "*"
+    }
+
+    /// Whether the two media types can describe the same content.
+    /// A wildcard on either side matches any type or subtype and
+    /// parameters only have to agree when both sides set them.
+    /// An invalid media type only matches the same text.
+    pub fn matches(&self, other: &MediaType<'_>) -> bool {
+        if !self.is_valid() || !other.is_valid() {
+            return self.raw.eq_ignore_ascii_case(&other.raw);
+        }
+
+        let part_matches = |left: &str, right: &str| {
+            left == "*" || right == "*" || left.eq_ignore_ascii_case(right)
+        };
+
+        part_matches(self.main_type(), other.main_type())
+            && part_matches(self.subtype(), other.subtype())
+            && self.parameters().all(|(name, value)| {
+                other
+                    .parameter(name)
+                    .is_none_or(|other| Folded::value(name, value) == Folded::value(name, other))
+            })
+    }
+
+    /// The parts that are compared, an invalid media type being a single part.
+    fn folded(&self) -> impl Iterator<Item = Folded<'_>> {
+        let essence = match self.is_valid() {
+            true => vec![self.main_type(), self.subtype()],
+            false => vec![self.as_str()],
+        };
+
+        essence
+            .into_iter()
+            .map(Folded::ignoring_case)
+            .chain(self.parameters().flat_map(|(name, value)| {
+                iter::once(Folded::ignoring_case(name))
+                    .chain(iter::once(Folded::value(name, value)))
+            }))
+    }
+}
+
+/// A part of a media type as it is compared.
+#[derive(Clone, Copy)]
+struct Folded<'a> {
+    text: &'a str,
+    ignore_case: bool,
+}
+
+impl<'a> Folded<'a> {
+    fn ignoring_case(text: &'a str) -> Self {
+        Self {
+            text,
+            ignore_case: true,
+        }
+    }
+
+    /// Parameter values are case-sensitive, except for `charset`.
+    fn value(name: &str, text: &'a str) -> Self {
+        Self {
+            text,
+            ignore_case: name.eq_ignore_ascii_case("charset"),
+        }
+    }
+
+    fn bytes(self) -> impl Iterator<Item = u8> + 'a {
+        self.text.bytes().map(move |byte| match self.ignore_case {
+            true => byte.to_ascii_lowercase(),
+            false => byte,
+        })
+    }
+}
+
+impl PartialEq for Folded<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes().eq(other.bytes())
+    }
+}
+
+impl Eq for Folded<'_> {}
+
+impl PartialOrd for Folded<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Folded<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes().cmp(other.bytes())
+    }
+}
+
+impl Hash for Folded<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text.len().hash(state);
+        self.bytes().for_each(|byte| byte.hash(state));
+    }
+}
+
+impl<'a, 'b> PartialEq<MediaType<'b>> for MediaType<'a> {
+    fn eq(&self, other: &MediaType<'b>) -> bool {
+        self.folded().eq(other.folded())
+    }
+}
+
+impl<'a> Eq for MediaType<'a> {}
+
+impl<'a> PartialOrd for MediaType<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for MediaType<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded().cmp(other.folded())
+    }
+}
+
+impl<'a> Hash for MediaType<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.folded().for_each(|part| part.hash(state));
+    }
+}
+
+/// The output modes an agent may answer with: the `offered` modes that match one of
+/// the `accepted` modes of the client, in the order of preference of the client.
+///
+/// When one side of a match is a wildcard the more specific media type is returned.
+/// A client that accepts no mode in particular gets all of the `offered` modes.
+/// When no mode matches the error is a `ContentTypeNotSupportedError`.
+pub fn negotiate_output_modes<'a>(
+    accepted: &[MediaType<'a>],
+    offered: &[MediaType<'a>],
+) -> Result<Vec<MediaType<'a>>, JsonRpcError<'a>> {
+    if accepted.is_empty() {
+        return Ok(offered.to_vec());
+    }
+
+    let mut negotiated = Vec::<MediaType<'a>>::new();
+
+    for accepted in accepted {
+        for offered in offered.iter().filter(|offered| offered.matches(accepted)) {
+            let mode = match offered.is_wildcard() && !accepted.is_wildcard() {
+                true => accepted,
+                false => offered,
+            };
+
+            if !negotiated.contains(mode) {
+                negotiated.push(mode.clone());
+            }
+        }
+    }
+
+    if negotiated.is_empty() {
+        let accepted = accepted
+            .iter()
+            .map(MediaType::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        return Err(JsonRpcError::content_type_not_supported(accepted));
+    }
+
+    Ok(negotiated)
+}
+
+/// The index of the `/` and the end of the subtype of `raw`, as defined by RFC 9110
+/// `type "/" subtype *( ";" parameter )`.
+fn split(raw: &str) -> Result<(usize, usize), &'static str> {
+    let essence_end = raw.find(';').unwrap_or(raw.len());
+    let slash = raw[..essence_end].find('/').ok_or("the `/` is missing")?;
+    let (main_type, subtype) = (&raw[..slash], raw[slash + 1..essence_end].trim_end());
+
+    if !is_token(main_type) || !is_token(subtype) {
+        return Err("the type and subtype must be non-empty tokens");
+    }
+    if main_type == "*" && subtype != "*" {
+        return Err("a wildcard type requires a wildcard subtype");
+    }
+    parse_parameters(&raw[essence_end..])?;
+
+    Ok((slash, slash + 1 + subtype.len()))
+}
+
+/// The `name=value` pairs of the text after the subtype, which starts with `;` unless it is empty.
+fn parse_parameters(mut rest: &str) -> Result<Vec<(&str, &str)>, &'static str> {
+    let mut parameters = Vec::new();
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(parameters);
+        }
+
+        rest = rest
+            .strip_prefix(';')
+            .ok_or("parameters must be separated by `;`")?
+            .trim_start();
+        if rest.is_empty() {
+            return Ok(parameters);
+        }
+
+        let (name, value) = rest.split_once('=').ok_or("a parameter has no value")?;
+        if !is_token(name) {
+            return Err("a parameter name must be a token");
+        }
+
+        let (value, remainder) = match value.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted
+                    .char_indices()
+                    .scan(false, |escaped, (index, char)| {
+                        let end = !*escaped && char == '"';
+                        *escaped = !*escaped && char == '\\';
+
+                        Some((index, end))
+                    })
+                    .find_map(|(index, end)| end.then_some(index))
+                    .ok_or("a quoted parameter value is not closed")?;
+
+                (&quoted[..end], &quoted[end + 1..])
+            }
+            None => {
+                let end = value.find(';').unwrap_or(value.len());
+                let token = value[..end].trim_end();
+                if !is_token(token) {
+                    return Err("a parameter value must be a token or a quoted string");
+                }
+
+                (token, &value[end..])
+            }
+        };
+
+        parameters.push((name, value));
+        rest = remainder;
+    }
+}
+
+/// RFC 9110 `token`.
+fn is_token(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte))
+}
+
+impl<'a> TryFrom<&'a str> for MediaType<'a> {
+    type Error = MediaTypeError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for MediaType<'static> {
+    type Error = MediaTypeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl<'a> PartialEq<str> for MediaType<'a> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&str> for MediaType<'a> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<'a> fmt::Display for MediaType<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl<'a> Serialize for MediaType<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for MediaType<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let JsonKey(raw) = JsonKey::deserialize(deserializer)?;
+
+        Ok(Self::lenient(raw))
+    }
+}
+
+impl<'a> IntoOwned for MediaType<'a> {
+    type Owned = MediaType<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        MediaType {
+            raw: IntoOwned::into_owned(self.raw),
+            slash: self.slash,
+            essence_end: self.essence_end,
+            invalid: self.invalid,
+        }
+    }
+}
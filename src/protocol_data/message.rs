@@ -1,7 +1,14 @@
-use serde::{Deserialize, Serialize};
-use std::{borrow::Cow, collections::BTreeMap};
+use serde::{
+    de::{self, IntoDeserializer},
+    Deserialize, Deserializer, Serialize,
+};
+use std::borrow::Cow;
 
-use crate::{JsonStr, JsonStrMemKV};
+use super::file_bytes;
+use crate::{
+    impl_into_owned, FileBytesError, FileDecoder, FileFetchError, FileFetcher, IntoOwned,
+    JsonObject, JsonStrMemKV, JsonValue, MediaType, MessageKind,
+};
 
 /// Represents a single communication turn or a piece of contextual information between a client and an agent.
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
@@ -15,28 +22,24 @@ pub struct Message<'a> {
     pub metadata: Option<JsonStrMemKV<'a>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(borrow)]
-    pub extensions: Option<Vec<&'a str>>,
+    pub extensions: Option<Vec<Cow<'a, str>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(borrow)]
-    pub reference_task_ids: Option<Vec<&'a str>>,
+    pub reference_task_ids: Option<Vec<Cow<'a, str>>>,
     #[serde(borrow)]
-    pub message_id: &'a str,
+    pub message_id: Cow<'a, str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(borrow)]
-    pub task_id: Option<&'a str>,
+    pub task_id: Option<Cow<'a, str>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(borrow)]
-    pub context_id: Option<&'a str>,
-    #[serde(borrow)]
-    pub kind: &'a str,
+    pub context_id: Option<Cow<'a, str>>,
+    pub kind: MessageKind,
 }
 
 impl<'a> Message<'a> {
     pub fn new() -> Self {
-        Self {
-            kind: "message",
-            ..Default::default()
-        }
+        Self::default()
     }
 }
 
@@ -105,19 +108,64 @@ pub struct DataPart<'a> {
     #[serde(borrow)]
     pub base: PartBase<'a>,
     #[serde(borrow)]
-    pub data: BTreeMap<&'a str, JsonStr<'a>>,
+    pub data: JsonObject<'a>,
 }
 
 /// File content as either bytes or URI
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+///
+/// Exactly one of `bytes` and `uri` must be present, a file object with both or neither is rejected.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize)]
 #[serde(untagged)]
 pub enum FileWith<'a> {
-    #[serde(borrow)]
     Bytes(FileWithBytes<'a>),
-    #[serde(borrow)]
     Uri(FileWithUri<'a>),
 }
 
+impl<'a> FileWith<'a> {
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::Bytes(file) => file.name(),
+            Self::Uri(file) => file.name(),
+        }
+    }
+
+    pub fn mime_type(&self) -> Option<&MediaType<'a>> {
+        match self {
+            Self::Bytes(file) => file.mime_type(),
+            Self::Uri(file) => file.mime_type(),
+        }
+    }
+
+    /// The file with its content inline, fetching it with `fetcher` if it is referenced by a uri.
+    pub fn resolve<F>(self, fetcher: &F) -> Result<FileWithBytes<'a>, FileFetchError>
+    where
+        F: FileFetcher + ?Sized,
+    {
+        match self {
+            Self::Bytes(file) => Ok(file),
+            Self::Uri(file) => file.resolve(fetcher),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for FileWith<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = JsonValue::deserialize(deserializer)?;
+
+        match (value.get("bytes").is_some(), value.get("uri").is_some()) {
+            (true, false) => FileWithBytes::deserialize(value.into_deserializer()).map(Self::Bytes),
+            (false, true) => FileWithUri::deserialize(value.into_deserializer()).map(Self::Uri),
+            (true, true) => Err(de::Error::custom(
+                "a file must have either `bytes` or `uri`, not both",
+            )),
+            (false, false) => Err(de::Error::custom("a file must have `bytes` or `uri`")),
+        }
+    }
+}
+
 impl<'a> Default for FileWith<'a> {
     fn default() -> Self {
         Self::Uri(FileWithUri::default())
@@ -128,25 +176,171 @@ impl<'a> Default for FileWith<'a> {
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
 pub struct FileWithBytes<'a> {
     #[serde(flatten)]
+    #[serde(borrow)]
     base: FileBase<'a>,
+    #[serde(borrow)]
     bytes: Cow<'a, str>,
     // uri is absent (handled by never type in TS)
 }
 
+impl<'a> FileWithBytes<'a> {
+    /// Encode `content` as base64.
+    pub fn new(content: &[u8], mime_type: MediaType<'a>) -> Self {
+        Self::from_base64(file_bytes::encode(content), mime_type)
+    }
+
+    /// Wrap content that is already base64-encoded. It is only checked when it is decoded.
+    pub fn from_base64(bytes: impl Into<Cow<'a, str>>, mime_type: MediaType<'a>) -> Self {
+        Self {
+            base: FileBase::new(mime_type),
+            bytes: bytes.into(),
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.base.name = Some(name.into());
+
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.base.name.as_deref()
+    }
+
+    pub fn mime_type(&self) -> Option<&MediaType<'a>> {
+        self.base.mime_type.as_ref()
+    }
+
+    /// The base64-encoded content of the file.
+    pub fn bytes(&self) -> &str {
+        &self.bytes
+    }
+
+    /// Decode the content without a size limit, see [FileDecoder] to set one.
+    pub fn decode(&self) -> Result<Vec<u8>, FileBytesError> {
+        FileDecoder::new().decode(self)
+    }
+
+    pub(crate) fn bytes_mut(&mut self) -> &mut Cow<'a, str> {
+        &mut self.bytes
+    }
+}
+
 /// File with URI reference
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
 pub struct FileWithUri<'a> {
     #[serde(flatten)]
+    #[serde(borrow)]
     base: FileBase<'a>,
+    #[serde(borrow)]
     uri: Cow<'a, str>,
     // bytes is absent (handled by never type in TS)
 }
 
+impl<'a> FileWithUri<'a> {
+    pub fn new(uri: impl Into<Cow<'a, str>>, mime_type: MediaType<'a>) -> Self {
+        Self {
+            base: FileBase::new(mime_type),
+            uri: uri.into(),
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.base.name = Some(name.into());
+
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.base.name.as_deref()
+    }
+
+    pub fn mime_type(&self) -> Option<&MediaType<'a>> {
+        self.base.mime_type.as_ref()
+    }
+
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Fetch the content with `fetcher`. The name and media type are kept, a file without
+    /// a media type gets the one reported by the fetcher or else `application/octet-stream`.
+    pub fn resolve<F>(self, fetcher: &F) -> Result<FileWithBytes<'a>, FileFetchError>
+    where
+        F: FileFetcher + ?Sized,
+    {
+        let fetched = fetcher.fetch(&self.uri)?;
+        let mut base = self.base;
+        base.mime_type = base
+            .mime_type
+            .or(fetched.mime_type)
+            .or(Some(MediaType::APPLICATION_OCTET_STREAM));
+
+        Ok(FileWithBytes {
+            base,
+            bytes: Cow::Owned(file_bytes::encode(&fetched.content)),
+        })
+    }
+}
+
 /// Base properties for a file
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FileBase<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<Cow<'a, str>>,
+    #[serde(borrow)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    mime_type: Option<Cow<'a, str>>,
+    mime_type: Option<MediaType<'a>>,
+}
+
+impl<'a> FileBase<'a> {
+    fn new(mime_type: MediaType<'a>) -> Self {
+        Self {
+            name: None,
+            mime_type: Some(mime_type),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Part<'a> {
+    type Owned = Part<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Self::Text(part) => Part::Text(part.into_owned()),
+            Self::File(part) => Part::File(part.into_owned()),
+            Self::Data(part) => Part::Data(part.into_owned()),
+        }
+    }
+}
+
+impl<'a> IntoOwned for FileWith<'a> {
+    type Owned = FileWith<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Self::Bytes(file) => FileWith::Bytes(file.into_owned()),
+            Self::Uri(file) => FileWith::Uri(file.into_owned()),
+        }
+    }
 }
+
+impl_into_owned!(Message {
+    role,
+    parts,
+    metadata,
+    extensions,
+    reference_task_ids,
+    message_id,
+    task_id,
+    context_id,
+    kind,
+});
+impl_into_owned!(PartBase { metadata });
+impl_into_owned!(TextPart { base, text });
+impl_into_owned!(FilePart { base, file });
+impl_into_owned!(DataPart { base, data });
+impl_into_owned!(FileWithBytes { base, bytes });
+impl_into_owned!(FileWithUri { base, uri });
+impl_into_owned!(FileBase { name, mime_type });
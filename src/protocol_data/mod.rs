@@ -10,8 +10,20 @@ pub use task_state::*;
 mod message;
 pub use message::*;
 
+mod kind;
+pub use kind::*;
+
 mod artifact;
 pub use artifact::*;
 
+mod media_type;
+pub use media_type::*;
+
+mod file_bytes;
+pub use file_bytes::*;
+
+mod file_fetcher;
+pub use file_fetcher::*;
+
 mod push_notification;
 pub use push_notification::*;
@@ -1,16 +1,43 @@
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
+use crate::impl_into_owned;
+
 /// Used as the params object for the tasks/pushNotificationConfig/set method
 /// and as the result object for the tasks/pushNotificationConfig/get method.
 /// A container associating a push notification configuration with a specific task.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Deserialize, Serialize, Hash)]
+#[serde(rename_all = "camelCase")]
 pub struct TaskPushNotificationConfig<'a> {
     /// The unique identifier (e.g. UUID) of the task.
-    task_id: &'a str,
+    #[serde(borrow)]
+    task_id: Cow<'a, str>,
     /** The push notification configuration for this task. */
+    #[serde(borrow)]
     push_notification_config: PushNotificationConfig<'a>,
 }
 
+impl<'a> TaskPushNotificationConfig<'a> {
+    pub fn new(
+        task_id: impl Into<Cow<'a, str>>,
+        push_notification_config: PushNotificationConfig<'a>,
+    ) -> Self {
+        Self {
+            task_id: task_id.into(),
+            push_notification_config,
+        }
+    }
+
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    pub fn push_notification_config(&self) -> &PushNotificationConfig<'a> {
+        &self.push_notification_config
+    }
+}
+
 /// Configuration provided by the client to the server for sending asynchronous push notifications about task updates.
 /// Defines the configuration for setting up push notifications for task updates.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Deserialize, Serialize, Hash)]
@@ -18,12 +45,15 @@ pub struct PushNotificationConfig<'a> {
     /// A unique identifier (e.g. UUID) for the push notification configuration, set by the client
     /// to support multiple notification callbacks.
     #[serde(skip_serializing_if = "Option::is_none")]
-    id: Option<&'a str>,
+    #[serde(borrow)]
+    id: Option<Cow<'a, str>>,
     /// The callback URL where the agent should send push notifications.
-    url: &'a str,
+    #[serde(borrow)]
+    url: Cow<'a, str>,
     ///  A unique token for this task or session to validate incoming push notifications.
     #[serde(skip_serializing_if = "Option::is_none")]
-    token: Option<&'a str>,
+    #[serde(borrow)]
+    token: Option<Cow<'a, str>>,
     /// Optional authentication details for the agent to use when calling the notification URL.
     #[serde(skip_serializing_if = "Option::is_none")]
     authentication: Option<PushNotificationAuthenticationInfo<'a>>,
@@ -35,8 +65,100 @@ pub struct PushNotificationConfig<'a> {
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Deserialize, Serialize, Hash)]
 pub struct PushNotificationAuthenticationInfo<'a> {
     /// A list of supported authentication schemes (e.g., 'Basic', 'Bearer').
-    schemes: Vec<&'a str>,
+    #[serde(borrow)]
+    schemes: Vec<Cow<'a, str>>,
     /// Optional credentials required by the push notification endpoint.
     #[serde(skip_serializing_if = "Option::is_none")]
-    credentials: Option<&'a str>,
+    #[serde(borrow)]
+    credentials: Option<Cow<'a, str>>,
 }
+
+impl<'a> PushNotificationConfig<'a> {
+    pub fn new(url: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            id: None,
+            url: url.into(),
+            token: None,
+            authentication: None,
+        }
+    }
+
+    pub fn with_id(mut self, id: impl Into<Cow<'a, str>>) -> Self {
+        self.id = Some(id.into());
+
+        self
+    }
+
+    pub fn with_token(mut self, token: impl Into<Cow<'a, str>>) -> Self {
+        self.token = Some(token.into());
+
+        self
+    }
+
+    pub fn with_authentication(
+        mut self,
+        authentication: PushNotificationAuthenticationInfo<'a>,
+    ) -> Self {
+        self.authentication = Some(authentication);
+
+        self
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    pub fn authentication(&self) -> Option<&PushNotificationAuthenticationInfo<'a>> {
+        self.authentication.as_ref()
+    }
+}
+
+impl<'a> PushNotificationAuthenticationInfo<'a> {
+    pub fn new<I>(schemes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Cow<'a, str>>,
+    {
+        Self {
+            schemes: schemes.into_iter().map(Into::into).collect(),
+            credentials: None,
+        }
+    }
+
+    pub fn with_credentials(mut self, credentials: impl Into<Cow<'a, str>>) -> Self {
+        self.credentials = Some(credentials.into());
+
+        self
+    }
+
+    pub fn schemes(&self) -> &[Cow<'a, str>] {
+        self.schemes.as_slice()
+    }
+
+    pub fn credentials(&self) -> Option<&str> {
+        self.credentials.as_deref()
+    }
+}
+
+impl_into_owned!(TaskPushNotificationConfig {
+    task_id,
+    push_notification_config,
+});
+impl_into_owned!(PushNotificationConfig {
+    id,
+    url,
+    token,
+    authentication,
+});
+impl_into_owned!(PushNotificationAuthenticationInfo {
+    schemes,
+    credentials,
+});
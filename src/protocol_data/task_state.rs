@@ -1,7 +1,11 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 /// Defines the lifecycle states of a Task.
-#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+#[derive(
+    Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Serialize, Deserialize,
+)]
 #[serde(rename_all = "kebab-case")]
 pub enum TaskState {
     /// The task has been submitted and is awaiting execution.
@@ -24,3 +28,73 @@ pub enum TaskState {
     #[default]
     Unknown,
 }
+
+impl TaskState {
+    pub const ALL: [Self; 9] = [
+        Self::Submitted,
+        Self::Working,
+        Self::InputRequired,
+        Self::Completed,
+        Self::Canceled,
+        Self::Failed,
+        Self::Rejected,
+        Self::AuthRequired,
+        Self::Unknown,
+    ];
+
+    /// The state as it appears on the wire, e.g. `input-required`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Submitted => "submitted",
+            Self::Working => "working",
+            Self::InputRequired => "input-required",
+            Self::Completed => "completed",
+            Self::Canceled => "canceled",
+            Self::Failed => "failed",
+            Self::Rejected => "rejected",
+            Self::AuthRequired => "auth-required",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// The task has finished and its state can no longer change.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Completed | Self::Canceled | Self::Failed | Self::Rejected
+        )
+    }
+
+    /// The task is paused until the client provides input or authentication.
+    pub fn is_interrupted(&self) -> bool {
+        matches!(self, Self::InputRequired | Self::AuthRequired)
+    }
+
+    /// A `tasks/cancel` request can still move the task to `canceled`.
+    pub fn is_cancelable(&self) -> bool {
+        !self.is_terminal()
+    }
+
+    /// Whether a task in this state may move to `next`:
+    /// - nothing leaves a terminal state
+    /// - a task only goes back to `submitted` when it is resumed after an interruption
+    /// - a task is only `rejected` before it is started
+    /// - any other move out of a non-terminal state is allowed, including staying in it
+    pub fn can_transition_to(&self, next: &TaskState) -> bool {
+        if self.is_terminal() {
+            return false;
+        }
+
+        match next {
+            Self::Submitted => self == next || self.is_interrupted() || *self == Self::Unknown,
+            Self::Rejected => matches!(self, Self::Submitted | Self::Unknown),
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for TaskState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -1,6 +1,8 @@
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
-use crate::{Message, TaskState};
+use crate::{impl_into_owned, Message, TaskState};
 
 /// Represents the current state and associated context (e.g., a message from the agent) of a Task.
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
@@ -15,5 +17,46 @@ pub struct TaskStatus<'a> {
     /// examples ["2023-10-27T10:00:00Z"]
     #[serde(borrow)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    timestamp: Option<&'a str>,
+    timestamp: Option<Cow<'a, str>>,
 }
+
+impl<'a> TaskStatus<'a> {
+    pub fn new(state: TaskState) -> Self {
+        Self {
+            state,
+            message: None,
+            timestamp: None,
+        }
+    }
+
+    pub fn with_message(mut self, message: Message<'a>) -> Self {
+        self.message = Some(message);
+
+        self
+    }
+
+    /// Set when the status was recorded, as an ISO 8601 datetime.
+    pub fn with_timestamp(mut self, timestamp: impl Into<Cow<'a, str>>) -> Self {
+        self.timestamp = Some(timestamp.into());
+
+        self
+    }
+
+    pub fn state(&self) -> &TaskState {
+        &self.state
+    }
+
+    pub fn message(&self) -> Option<&Message<'a>> {
+        self.message.as_ref()
+    }
+
+    pub fn timestamp(&self) -> Option<&str> {
+        self.timestamp.as_deref()
+    }
+}
+
+impl_into_owned!(TaskStatus {
+    state,
+    message,
+    timestamp,
+});
@@ -1,18 +1,22 @@
-use std::collections::BTreeMap;
+use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Artifact, JsonStr, Message, TaskStatus};
+use crate::{
+    impl_into_owned, Artifact, JsonRpcError, JsonRpcErrorCode, JsonStrMemKV, Message,
+    MissingFieldError, TaskKind, TaskState, TaskStatus, TaskTransitionError,
+};
 
 ///  Represents a single, stateful operation or conversation between a client and an agent.
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Task<'a> {
     /// A unique identifier (e.g. UUID) for the task, generated by the server for a new task.
     #[serde(borrow)]
-    id: &'a str,
+    id: Cow<'a, str>,
     /// A server-generated unique identifier (e.g. UUID) for maintaining context across multiple related tasks or interactions.
     #[serde(borrow)]
-    context_id: &'a str,
+    context_id: Cow<'a, str>,
     /// The current status of the task, including its state and a descriptive message.
     #[serde(borrow)]
     status: TaskStatus<'a>,
@@ -27,17 +31,221 @@ pub struct Task<'a> {
     /// Optional metadata for extensions. The key is an extension-specific identifier.
     #[serde(borrow)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    metadata: Option<BTreeMap<&'a str, JsonStr<'a>>>,
+    metadata: Option<JsonStrMemKV<'a>>,
     /// The type of this object, used as a discriminator. Always 'task' for a Task.
-    #[serde(borrow)]
-    kind: &'a str,
+    kind: TaskKind,
 }
 
 impl<'a> Task<'a> {
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start building a task. `id` and `contextId` are required, the status defaults to `submitted`.
+    pub fn builder() -> TaskBuilder<'a> {
+        TaskBuilder::default()
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn context_id(&self) -> &str {
+        &self.context_id
+    }
+
+    pub fn status(&self) -> &TaskStatus<'a> {
+        &self.status
+    }
+
+    pub fn history(&self) -> Option<&[Message<'a>]> {
+        self.history.as_deref()
+    }
+
+    pub fn artifacts(&self) -> Option<&[Artifact<'a>]> {
+        self.artifacts.as_deref()
+    }
+
+    pub fn metadata(&self) -> Option<&JsonStrMemKV<'a>> {
+        self.metadata.as_ref()
+    }
+
+    pub fn kind(&self) -> &'static str {
+        self.kind.as_str()
+    }
+
+    /// Move the task to `status` if its current state allows it, see [crate::TaskState::can_transition_to].
+    /// The task is left untouched when the transition is rejected.
+    pub fn apply_status(&mut self, status: TaskStatus<'a>) -> Result<(), TaskTransitionError<'a>> {
+        let from = *self.status.state();
+        let to = *status.state();
+
+        if !from.can_transition_to(&to) {
+            return Err(TaskTransitionError {
+                task_id: self.id.clone(),
+                from,
+                to,
+            });
+        }
+
+        self.status = status;
+
+        Ok(())
+    }
+
+    /// Trim the task as requested by `historyLength` and `includeArtifacts`.
+    pub fn project(mut self, projection: TaskProjection) -> Self {
+        if let (Some(history), Some(history_length)) =
+            (self.history.as_mut(), projection.history_length())
+        {
+            history.drain(..history.len().saturating_sub(history_length));
+        }
+
+        if !projection.include_artifacts() {
+            self.artifacts = None;
+        }
+
+        self
+    }
+
+    pub(crate) fn history_mut(&mut self) -> &mut Vec<Message<'a>> {
+        self.history.get_or_insert_with(Vec::new)
+    }
+
+    pub(crate) fn artifacts_mut(&mut self) -> &mut Vec<Artifact<'a>> {
+        self.artifacts.get_or_insert_with(Vec::new)
+    }
+}
+
+/// How a [Task] is trimmed before it is returned to a client, see [Task::project].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct TaskProjection {
+    history_length: Option<usize>,
+    include_artifacts: bool,
+}
+
+impl TaskProjection {
+    /// Keeps the whole task.
+    pub const ALL: Self = Self {
+        history_length: None,
+        include_artifacts: true,
+    };
+
+    /// Keep the `history_length` most recent messages, or the whole history when it is `None`,
+    /// and omit `artifacts` unless `include_artifacts` is set.
+    /// A negative `history_length` is an `InvalidParams` error.
+    pub fn new(
+        history_length: Option<i64>,
+        include_artifacts: bool,
+    ) -> Result<Self, JsonRpcError<'static>> {
+        let history_length = history_length
+            .map(usize::try_from)
+            .transpose()
+            .map_err(|_| {
+                JsonRpcError::new(JsonRpcErrorCode::InvalidParams)
+                    .with_message("`historyLength` must not be negative")
+            })?;
+
+        Ok(Self {
+            history_length,
+            include_artifacts,
+        })
+    }
+
+    pub fn history_length(&self) -> Option<usize> {
+        self.history_length
+    }
+
+    pub fn include_artifacts(&self) -> bool {
+        self.include_artifacts
+    }
+}
+
+impl Default for TaskProjection {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Builds a [Task], checking at [TaskBuilder::build] that the required fields are set.
+#[derive(Debug, Clone)]
+pub struct TaskBuilder<'a> {
+    task: Task<'a>,
+}
+
+impl<'a> TaskBuilder<'a> {
+    pub fn id(mut self, id: impl Into<Cow<'a, str>>) -> Self {
+        self.task.id = id.into();
+
+        self
+    }
+
+    pub fn context_id(mut self, context_id: impl Into<Cow<'a, str>>) -> Self {
+        self.task.context_id = context_id.into();
+
+        self
+    }
+
+    pub fn status(mut self, status: TaskStatus<'a>) -> Self {
+        self.task.status = status;
+
+        self
+    }
+
+    /// Append a message to `history`.
+    pub fn message(mut self, message: Message<'a>) -> Self {
+        self.task.history.get_or_insert_with(Vec::new).push(message);
+
+        self
+    }
+
+    pub fn artifact(mut self, artifact: Artifact<'a>) -> Self {
+        self.task
+            .artifacts
+            .get_or_insert_with(Vec::new)
+            .push(artifact);
+
+        self
+    }
+
+    pub fn metadata(mut self, metadata: JsonStrMemKV<'a>) -> Self {
+        self.task.metadata = Some(metadata);
+
+        self
+    }
+
+    pub fn build(self) -> Result<Task<'a>, MissingFieldError> {
+        let task = self.task;
+
+        MissingFieldError::check(
+            "Task",
+            [
+                ("id", !task.id.is_empty()),
+                ("contextId", !task.context_id.is_empty()),
+            ],
+        )?;
+
+        Ok(task)
+    }
+}
+
+impl<'a> Default for TaskBuilder<'a> {
+    fn default() -> Self {
         Self {
-            kind: "task",
-            ..Default::default()
+            task: Task {
+                status: TaskStatus::new(TaskState::Submitted),
+                ..Task::new()
+            },
         }
     }
 }
+
+impl_into_owned!(Task {
+    id,
+    context_id,
+    status,
+    history,
+    artifacts,
+    metadata,
+    kind,
+});
@@ -125,3 +125,2571 @@ mod agent_card_sanity_checks {
       }
 "#;
 }
+
+#[cfg(test)]
+mod jsonrpc_sanity_checks {
+    use crate::jsonrpc::{
+        A2aMethod, A2aResponse, DeleteTaskPushNotificationConfigParams, JsonRpcId, JsonRpcMethods,
+        JsonRpcRequest, TaskQueryParams, TasksGet, TasksPushNotificationConfigDelete,
+    };
+
+    #[test]
+    fn methods_round_trip() {
+        for method in JsonRpcMethods::ALL {
+            assert_eq!(JsonRpcMethods::try_from(method.as_str()), Ok(method));
+
+            let serialized = serde_json::to_string(&method).unwrap();
+            assert_eq!(serialized, format!("\"{}\"", method.as_str()));
+            assert_eq!(
+                serde_json::from_str::<JsonRpcMethods>(&serialized).unwrap(),
+                method
+            );
+        }
+
+        assert_eq!(
+            JsonRpcMethods::try_from("tasks/foo").unwrap_err().code,
+            crate::JsonRpcErrorCode::MethodNotFound
+        );
+    }
+
+    #[test]
+    fn typed_request_method() {
+        let request = serde_json::from_str::<JsonRpcRequest<()>>(
+            r#"{"jsonrpc": "2.0", "method": "tasks/pushNotificationConfig/list", "id": 1}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request.method,
+            JsonRpcMethods::TasksPushNotificationConfigList
+        );
+
+        assert!(serde_json::from_str::<JsonRpcRequest<()>>(
+            r#"{"jsonrpc": "2.0", "method": "tasks/foo", "id": 1}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn task_id_params() {
+        let params = serde_json::from_str::<DeleteTaskPushNotificationConfigParams>(
+            r#"{"id": "task-1", "pushNotificationConfigId": "cfg-1", "metadata": {"k": "v"}}"#,
+        )
+        .unwrap();
+        assert_eq!(params.id(), "task-1");
+        assert_eq!(params.push_notification_config_id, "cfg-1");
+        assert!(params.base.metadata.is_some());
+
+        assert_eq!(
+            serde_json::to_string(&DeleteTaskPushNotificationConfigParams::new(
+                "task-1", "cfg-1"
+            ))
+            .unwrap(),
+            r#"{"id":"task-1","pushNotificationConfigId":"cfg-1"}"#
+        );
+    }
+
+    #[test]
+    fn typed_method_request_and_response() {
+        let request = TasksGet::request(
+            TaskQueryParams::new("task-1").with_history_length(2),
+            JsonRpcId::Number(7),
+        );
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"jsonrpc":"2.0","method":"tasks/get","params":{"id":"task-1","historyLength":2},"id":7}"#
+        );
+
+        let response = serde_json::from_str::<A2aResponse<TasksPushNotificationConfigDelete>>(
+            r#"{"jsonrpc": "2.0", "id": "abc", "result": null}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            TasksPushNotificationConfigDelete::response((), JsonRpcId::String("abc".into()))
+        );
+    }
+}
+
+#[cfg(test)]
+mod router_sanity_checks {
+    use crate::{
+        jsonrpc::{
+            A2aHandler, HandlerResult, JsonRpcBatch, JsonRpcBatchRequest, JsonRpcRouter,
+            MessageSendParams, RouterResponse, SendMessageResult, TaskQueryParams,
+        },
+        JsonRpcError, Task,
+    };
+
+    struct EchoAgent;
+
+    impl A2aHandler for EchoAgent {
+        fn message_send<'a>(
+            &'a self,
+            params: MessageSendParams<'a>,
+        ) -> HandlerResult<'a, SendMessageResult<'a>> {
+            Ok(SendMessageResult::Message(params.message))
+        }
+
+        fn tasks_get<'a>(&'a self, params: TaskQueryParams<'a>) -> HandlerResult<'a, Task<'a>> {
+            Err(JsonRpcError::task_not_found(params.base.id))
+        }
+    }
+
+    fn route(request: &str) -> serde_json::Value {
+        match JsonRpcRouter::new(EchoAgent).handle(request.as_bytes()) {
+            RouterResponse::Single(body) => serde_json::from_slice(&body).unwrap(),
+            response => panic!("Expected a single response, found {response:?}"),
+        }
+    }
+
+    #[test]
+    fn dispatches_to_handler() {
+        let response = route(
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "message/send", "params": {"message":
+            {"role": "user", "parts": [{"kind": "text", "text": "hi"}], "messageId": "m-1", "kind": "message"}}}"#,
+        );
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["messageId"], "m-1");
+
+        let response = route(
+            r#"{"jsonrpc": "2.0", "id": "a", "method": "tasks/get", "params": {"id": "task-1"}}"#,
+        );
+        assert_eq!(response["id"], "a");
+        assert_eq!(response["error"]["code"], -32001);
+    }
+
+    #[test]
+    fn error_codes() {
+        let response = route(r#"{"jsonrpc": "2.0", "id": 1, "method": "#);
+        assert_eq!(response["error"]["code"], -32700);
+        assert!(response["id"].is_null());
+
+        let response = route(r#"{"jsonrpc": "2.0", "id": 1}"#);
+        assert_eq!(response["error"]["code"], -32600);
+
+        let response = route(r#"{"jsonrpc": "2.0", "id": 1, "method": "tasks/foo"}"#);
+        assert_eq!(response["error"]["code"], -32601);
+
+        let response =
+            route(r#"{"jsonrpc": "2.0", "id": 1, "method": "message/send", "params": {}}"#);
+        assert_eq!(response["error"]["code"], -32602);
+
+        let response = route(
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "tasks/cancel", "params": {"id": "t"}}"#,
+        );
+        assert_eq!(response["error"]["code"], -32004);
+    }
+
+    #[test]
+    fn notifications_get_no_response() {
+        let router = JsonRpcRouter::new(EchoAgent);
+        let response = router
+            .handle(br#"{"jsonrpc": "2.0", "method": "tasks/get", "params": {"id": "task-1"}}"#);
+        assert!(matches!(response, RouterResponse::None));
+
+        // Invalid params of a notification are not answered either.
+        let response =
+            router.handle(br#"{"jsonrpc": "2.0", "method": "tasks/get", "params": {"nope": 1}}"#);
+        assert!(matches!(response, RouterResponse::None));
+        let response =
+            router.handle(br#"{"jsonrpc": "2.0", "method": "message/stream", "params": {}}"#);
+        assert!(matches!(response, RouterResponse::None));
+    }
+
+    #[test]
+    fn batches() {
+        let response = route(
+            r#"[
+                {"jsonrpc": "2.0", "id": 1, "method": "tasks/get", "params": {"id": "task-1"}},
+                {"jsonrpc": "2.0", "method": "tasks/get", "params": {"id": "task-1"}},
+                {"jsonrpc": "2.0", "id": 2, "method": "message/stream", "params": {}},
+                7
+            ]"#,
+        );
+        let responses = response.as_array().unwrap();
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[0]["error"]["code"], -32001);
+        assert_eq!(responses[1]["id"], 2);
+        assert_eq!(responses[1]["error"]["code"], -32600);
+        assert!(responses[2]["id"].is_null());
+        assert_eq!(responses[2]["error"]["code"], -32600);
+
+        let response = route("[]");
+        assert!(response["id"].is_null());
+        assert_eq!(response["error"]["code"], -32600);
+
+        let response = route("[1");
+        assert!(response["id"].is_null());
+        assert_eq!(response["error"]["code"], -32700);
+
+        let response = route(r#"[{"jsonrpc": "2.0", "method": 1}]"#);
+        assert!(response[0]["id"].is_null());
+        assert_eq!(response[0]["error"]["code"], -32600);
+
+        let router = JsonRpcRouter::new(EchoAgent);
+        let response = router
+            .handle(br#"[{"jsonrpc": "2.0", "method": "tasks/get", "params": {"id": "task-1"}}]"#);
+        assert!(matches!(response, RouterResponse::None));
+    }
+
+    #[test]
+    fn batch_envelopes() {
+        let batch = serde_json::from_str::<JsonRpcBatchRequest<TaskQueryParams>>(
+            r#"[{"jsonrpc": "2.0", "id": 1, "method": "tasks/get", "params": {"id": "task-1"}},
+                {"jsonrpc": "2.0", "id": 2, "method": "tasks/get", "params": {"id": "task-1"}}]"#,
+        )
+        .unwrap();
+        assert_eq!(batch.len(), 2);
+
+        let single = serde_json::from_str::<JsonRpcBatchRequest<TaskQueryParams>>(
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "tasks/get", "params": {"id": "task-1"}}"#,
+        )
+        .unwrap();
+        assert!(matches!(single, JsonRpcBatch::Single(_)));
+
+        let empty = serde_json::from_str::<JsonRpcBatchRequest<TaskQueryParams>>("[]").unwrap();
+        assert!(empty.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod error_sanity_checks {
+    use crate::{jsonrpc, JsonRpcError, JsonRpcErrorCode};
+
+    #[test]
+    fn codes_round_trip() {
+        for code in [
+            -32700, -32601, -32001, -32007, -32000, -32050, -32099, -31999, 42,
+        ] {
+            assert_eq!(i64::from(JsonRpcErrorCode::from(code)), code);
+        }
+
+        assert_eq!(
+            JsonRpcErrorCode::from(-32001),
+            JsonRpcErrorCode::TaskNotFoundError
+        );
+        assert_eq!(
+            JsonRpcErrorCode::from(-32050),
+            JsonRpcErrorCode::ServerError(-32050)
+        );
+        assert_eq!(JsonRpcErrorCode::from(-1), JsonRpcErrorCode::Unknown(-1));
+    }
+
+    #[test]
+    fn wire_errors() {
+        let error = JsonRpcError::from(JsonRpcErrorCode::InvalidParams);
+        assert_eq!(error.message, JsonRpcErrorCode::InvalidParams.description());
+        assert_eq!(
+            JsonRpcErrorCode::from(error),
+            JsonRpcErrorCode::InvalidParams
+        );
+
+        assert_eq!(
+            serde_json::to_string(&JsonRpcError::task_not_found("task-1")).unwrap(),
+            format!(
+                r#"{{"code":-32001,"message":"{}","data":{{"taskId":"task-1"}}}}"#,
+                JsonRpcErrorCode::TaskNotFoundError.description()
+            )
+        );
+
+        let error = serde_json::from_str::<jsonrpc::JsonRpcError>(
+            r#"{"code": -32042, "message": "Quota exceeded", "data": {"retryAfter": 30, "scopes": ["a"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(error.code, JsonRpcErrorCode::ServerError(-32042));
+        assert_eq!(
+            error.data.as_ref().and_then(|data| data.get("retryAfter")),
+            Some(&30i64.into())
+        );
+        assert!(serde_json::to_string(&error)
+            .unwrap()
+            .contains(r#""code":-32042"#));
+    }
+}
+
+#[cfg(test)]
+mod envelope_sanity_checks {
+    use crate::{
+        jsonrpc::{JsonRpcId, JsonRpcPayload, JsonRpcRequest, JsonRpcResponse, TaskQueryParams},
+        JsonRpcErrorCode,
+    };
+
+    fn request_error(request: &str) -> (Option<JsonRpcId>, JsonRpcErrorCode) {
+        let error =
+            JsonRpcRequest::<TaskQueryParams>::from_slice_strict(request.as_bytes()).unwrap_err();
+        (error.id.clone(), error.code())
+    }
+
+    fn response_error(response: &str) -> JsonRpcErrorCode {
+        JsonRpcResponse::<serde_json::Value>::from_slice_strict(response.as_bytes())
+            .unwrap_err()
+            .code()
+    }
+
+    #[test]
+    fn strict_requests() {
+        let request = JsonRpcRequest::<TaskQueryParams>::from_slice_strict(
+            br#"{"jsonrpc": "2.0", "id": "r-1", "method": "tasks/get", "params": {"id": "task-1"}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.id, Some(JsonRpcId::String("r-1".into())));
+
+        assert_eq!(
+            request_error(r#"{"jsonrpc": "2.0", "id": 1, "method": "tasks/get""#),
+            (Some(JsonRpcId::Null), JsonRpcErrorCode::ParseError)
+        );
+        assert_eq!(
+            request_error(r#"{"jsonrpc": "1.0", "id": 1, "method": "tasks/get"}"#),
+            (Some(JsonRpcId::Number(1)), JsonRpcErrorCode::InvalidRequest)
+        );
+        assert_eq!(
+            request_error(r#"{"jsonrpc": "2.0", "id": 1.5, "method": "tasks/get"}"#),
+            (Some(JsonRpcId::Null), JsonRpcErrorCode::InvalidRequest)
+        );
+        assert_eq!(
+            request_error(r#"{"jsonrpc": "2.0", "id": {}, "method": "tasks/get"}"#),
+            (Some(JsonRpcId::Null), JsonRpcErrorCode::InvalidRequest)
+        );
+        assert_eq!(
+            request_error(r#"{"jsonrpc": "2.0", "id": 1, "method": "tasks/get", "params": "x"}"#),
+            (Some(JsonRpcId::Number(1)), JsonRpcErrorCode::InvalidRequest)
+        );
+        // Invalid requests without an `id` are not notifications.
+        assert_eq!(
+            request_error(r#"{"jsonrpc": "2.0", "method": 1, "params": "bar"}"#),
+            (Some(JsonRpcId::Null), JsonRpcErrorCode::InvalidRequest)
+        );
+        assert_eq!(
+            request_error(r#"{"jsonrpc": "1.0", "method": "tasks/get"}"#),
+            (Some(JsonRpcId::Null), JsonRpcErrorCode::InvalidRequest)
+        );
+        assert_eq!(
+            request_error(r#"{"jsonrpc": "2.0", "id": 1, "method": "tasks/foo"}"#),
+            (Some(JsonRpcId::Number(1)), JsonRpcErrorCode::MethodNotFound)
+        );
+        assert_eq!(
+            request_error(r#"{"jsonrpc": "2.0", "method": "tasks/get", "params": []}"#),
+            (None, JsonRpcErrorCode::InvalidParams)
+        );
+    }
+
+    #[test]
+    fn strict_responses() {
+        let response = JsonRpcResponse::<serde_json::Value>::from_slice_strict(
+            br#"{"jsonrpc": "2.0", "id": null, "error": {"code": -32700, "message": "Parse error"}}"#,
+        )
+        .unwrap();
+        assert!(matches!(response.payload, JsonRpcPayload::Error { .. }));
+
+        assert_eq!(
+            response_error(r#"{"jsonrpc": "2.0", "id": 1}"#),
+            JsonRpcErrorCode::InvalidAgentResponseError
+        );
+        assert_eq!(
+            response_error(
+                r#"{"jsonrpc": "2.0", "id": 1, "result": 1, "error": {"code": 1, "message": ""}}"#
+            ),
+            JsonRpcErrorCode::InvalidAgentResponseError
+        );
+        assert_eq!(
+            response_error(r#"{"jsonrpc": "2.0", "result": {}}"#),
+            JsonRpcErrorCode::InvalidAgentResponseError
+        );
+        assert_eq!(
+            response_error(
+                r#"{"jsonrpc": "2.0", "id": 3, "error": {"code": -32700, "message": ""}}"#
+            ),
+            JsonRpcErrorCode::InvalidAgentResponseError
+        );
+        assert_eq!(
+            response_error(r#"{"jsonrpc": "3.0", "id": 3, "result": {}}"#),
+            JsonRpcErrorCode::InvalidAgentResponseError
+        );
+        assert_eq!(response_error("nope"), JsonRpcErrorCode::ParseError);
+    }
+}
+
+#[cfg(test)]
+mod streaming_sanity_checks {
+    use crate::jsonrpc::{
+        JsonRpcPayload, JsonRpcResponse, SendMessageResult, SendStreamingMessageSuccessResponse,
+    };
+
+    const TASK: &str =
+        r#"{"id": "task-1", "contextId": "ctx-1", "status": {"state": "working"}, "kind": "task"}"#;
+    const MESSAGE: &str = r#"{"role": "agent", "parts": [{"kind": "text", "text": "hi"}], "messageId": "m-1", "kind": "message"}"#;
+    const STATUS_UPDATE: &str = r#"{"taskId": "task-1", "contextId": "ctx-1", "kind": "status-update", "status": {"state": "completed"}, "final": true}"#;
+    const ARTIFACT_UPDATE: &str = r#"{"taskId": "task-1", "contextId": "ctx-1", "kind": "artifact-update", "artifact": {"artifactId": "a-1", "parts": [{"kind": "text", "text": "chunk"}]}, "append": true}"#;
+
+    #[test]
+    fn send_message_result() {
+        let task = serde_json::from_str::<SendMessageResult>(TASK).unwrap();
+        assert_eq!(task.kind(), "task");
+
+        let message = serde_json::from_str::<SendMessageResult>(MESSAGE).unwrap();
+        assert_eq!(message.kind(), "message");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&message).unwrap())
+                .unwrap()["kind"],
+            "message"
+        );
+
+        assert!(serde_json::from_str::<SendMessageResult>(STATUS_UPDATE).is_err());
+        assert!(serde_json::from_str::<SendMessageResult>(r#"{"id": "task-1"}"#).is_err());
+    }
+
+    #[test]
+    fn streaming_response() {
+        for (event, kind) in [
+            (TASK, "task"),
+            (MESSAGE, "message"),
+            (STATUS_UPDATE, "status-update"),
+            (ARTIFACT_UPDATE, "artifact-update"),
+        ] {
+            let response = format!(r#"{{"jsonrpc": "2.0", "id": 1, "result": {event}}}"#);
+            let response = serde_json::from_str::<
+                JsonRpcResponse<SendStreamingMessageSuccessResponse>,
+            >(&response)
+            .unwrap();
+
+            match response.payload {
+                JsonRpcPayload::Success { result } => assert_eq!(result.kind(), kind),
+                JsonRpcPayload::Error { error } => panic!("Unexpected error {error}"),
+            }
+        }
+
+        assert!(serde_json::from_str::<SendStreamingMessageSuccessResponse>(
+            r#"{"kind": "status"}"#
+        )
+        .is_err());
+    }
+}
+
+#[cfg(test)]
+mod json_value_sanity_checks {
+    use std::borrow::Cow;
+
+    use crate::{JsonValue, Part, RawJson};
+
+    #[test]
+    fn structured_data_part() {
+        let part = serde_json::from_str::<Part>(
+            r#"{"kind": "data", "data": {"lat": 37.4, "tags": ["a", "b\"c"], "nested": {"ok": true, "n": null}}}"#,
+        )
+        .unwrap();
+
+        let Part::Data(data) = &part else {
+            panic!("Expected a data part, got {part:?}");
+        };
+        assert_eq!(data.data["lat"].as_number().unwrap().as_f64(), 37.4);
+        assert_eq!(
+            data.data["tags"].as_array().unwrap()[1].as_str(),
+            Some("b\"c")
+        );
+        assert!(matches!(
+            data.data["tags"].as_array().unwrap()[0],
+            JsonValue::String(Cow::Borrowed("a"))
+        ));
+        assert_eq!(data.data["nested"].get("ok").unwrap().as_bool(), Some(true));
+        assert!(data.data["nested"].get("n").unwrap().is_null());
+
+        let encoded = serde_json::to_string(&part).unwrap();
+        assert_eq!(serde_json::from_str::<Part>(&encoded).unwrap(), part);
+    }
+
+    #[test]
+    fn raw_passthrough() {
+        let raw = serde_json::from_str::<RawJson>(r#"{"b": 1,  "a": [2.50]}"#).unwrap();
+        assert_eq!(raw.get(), r#"{"b": 1,  "a": [2.50]}"#);
+
+        let value = JsonValue::from_iter([(Cow::Borrowed("payload"), JsonValue::Raw(raw.clone()))]);
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"payload":{"b": 1,  "a": [2.50]}}"#
+        );
+
+        let expanded = JsonValue::Raw(raw).expand().unwrap();
+        assert_eq!(
+            expanded.get("b").unwrap().as_number().unwrap().as_u64(),
+            Some(1)
+        );
+
+        let owned = RawJson::from_string("[1, true]".into()).unwrap();
+        assert_eq!(
+            JsonValue::Raw(owned)
+                .deserialize_into::<(u8, bool)>()
+                .unwrap(),
+            (1, true)
+        );
+        assert!(RawJson::from_string("{".into()).is_err());
+    }
+}
+
+#[cfg(test)]
+mod escaped_string_sanity_checks {
+    use std::borrow::Cow;
+
+    use crate::{
+        jsonrpc::{SendMessageResult, TaskQueryParams},
+        AgentCard, FilePart, FileWith, Message, Part, TransportProtocol,
+    };
+
+    #[test]
+    fn escaped_message() {
+        let message = serde_json::from_str::<Message>(
+            r#"{"role": "agent", "parts": [{"kind": "text", "text": "line\none \"quoted\" café"}], "messageId": "m-1", "contextId": "ctx-1", "kind": "message"}"#,
+        )
+        .unwrap();
+
+        let Part::Text(text) = &message.parts[0] else {
+            panic!("Expected a text part, got {:?}", message.parts[0]);
+        };
+        assert_eq!(text.text, "line\none \"quoted\" café");
+        assert_eq!(message.message_id, "m-1");
+        assert!(matches!(message.message_id, Cow::Borrowed(_)));
+        assert!(matches!(text.text, Cow::Owned(_)));
+    }
+
+    #[test]
+    fn escaped_agent_card() {
+        let card = serde_json::from_str::<AgentCard>(
+            r#"{
+                "protocolVersion": "0.3.0",
+                "name": "Recipe \"Chef\" Agent",
+                "description": "Helps with recipes.\nAsk it anything — really.",
+                "url": "https://example.com/a2a",
+                "preferredTransport": "GRP\u0043",
+                "additionalInterfaces": [{"url": "https://example.com/rest", "transport": "HTTP\u002bJSON"}],
+                "version": "1.0.0",
+                "capabilities": {"streaming": true, "pushNotifications": false},
+                "defaultInputModes": ["text/plain"],
+                "defaultOutputModes": ["text/plain"],
+                "skills": [{"id": "cook", "name": "Cook", "description": "Tab\tseparated", "tags": ["cöoking"]}]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(card.name(), "Recipe \"Chef\" Agent");
+        assert_eq!(
+            card.description(),
+            "Helps with recipes.\nAsk it anything — really."
+        );
+        assert_eq!(card.default_input_modes(), ["text/plain"]);
+        assert_eq!(card.preferred_transport(), Some(&TransportProtocol::Grpc));
+        assert_eq!(
+            card.additional_interfaces().unwrap()[0].transport,
+            TransportProtocol::HttpJson
+        );
+    }
+
+    #[test]
+    fn borrowed_file_bytes() {
+        let message = serde_json::from_str::<Message>(
+            r#"{"role": "user", "parts": [{"kind": "file", "file": {"bytes": "JVBERi0xLjc", "mimeType": "application/pdf"}}], "messageId": "m-1", "kind": "message"}"#,
+        )
+        .unwrap();
+
+        let Part::File(FilePart {
+            file: FileWith::Bytes(mut file),
+            ..
+        }) = message.parts[0].clone()
+        else {
+            panic!("Expected a file part, got {:?}", message.parts[0]);
+        };
+        assert!(matches!(file.bytes_mut(), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn escaped_buffered_values() {
+        let result = serde_json::from_str::<SendMessageResult>(
+            r#"{"id": "task\/1", "contextId": "ctx-1", "status": {"state": "working", "timestamp": "2025-01-01"}, "kind": "task"}"#,
+        )
+        .unwrap();
+        assert_eq!(result.kind(), "task");
+
+        let params =
+            serde_json::from_str::<TaskQueryParams>(r#"{"id": "task\/1", "historyLength": 2}"#)
+                .unwrap();
+        assert_eq!(params.id(), "task/1");
+        assert_eq!(params.history_length(), Some(2));
+    }
+}
+
+#[cfg(test)]
+mod owned_sanity_checks {
+    use crate::{jsonrpc::SendStreamingMessageSuccessResponse, AgentCard, IntoOwned, Task};
+
+    #[test]
+    fn owned_task_outlives_input() {
+        let body = String::from(
+            r#"{"id": "task-1", "contextId": "ctx-1", "status": {"state": "working"},
+                "history": [{"role": "user", "parts": [{"kind": "file", "file": {"uri": "https://example.com/a.pdf"}}, {"kind": "data", "data": {"n": 1}}], "messageId": "m-1", "kind": "message"}],
+                "metadata": {"source": "test"}, "kind": "task"}"#,
+        );
+        let task = serde_json::from_str::<Task>(&body).unwrap();
+        let expected = serde_json::to_string(&task).unwrap();
+        let task: Task<'static> = task.into_owned();
+        drop(body);
+
+        let task = std::thread::spawn(move || task).join().unwrap();
+        assert_eq!(serde_json::to_string(&task).unwrap(), expected);
+    }
+
+    #[test]
+    fn owned_nested_values() {
+        let event = r#"{"taskId": "t-1", "contextId": "c-1", "kind": "artifact-update", "artifact": {"artifactId": "a-1", "parts": [{"kind": "text", "text": "hi"}]}}"#;
+        let parsed = serde_json::from_str::<SendStreamingMessageSuccessResponse>(event).unwrap();
+        let owned: SendStreamingMessageSuccessResponse<'static> = parsed.clone().into_owned();
+        assert_eq!(owned, parsed);
+
+        let card = r#"{"protocolVersion": "0.3.0", "name": "a", "description": "b", "url": "https://example.com", "preferredTransport": "JSONRPC", "version": "1",
+            "capabilities": {"streaming": false, "pushNotifications": false},
+            "securitySchemes": {"key": {"type": "apiKey", "name": "X-Key", "in": "header", "x-rotation": "daily"}},
+            "defaultInputModes": [], "defaultOutputModes": [], "skills": []}"#;
+        let parsed = serde_json::from_str::<AgentCard>(card).unwrap();
+        let owned: AgentCard<'static> = parsed.clone().into_owned();
+        assert_eq!(owned, parsed);
+    }
+}
+
+#[cfg(test)]
+mod builder_sanity_checks {
+    use crate::{
+        jsonrpc::{SendStreamingMessageSuccessResponse, TaskStatusUpdateEvent},
+        AgentCard, AgentSkill, Artifact, MediaType, MissingFieldError, Part, Task, TaskState,
+        TaskStatus, TextPart,
+    };
+
+    fn skill() -> AgentSkill<'static> {
+        AgentSkill::builder()
+            .id("route-optimizer")
+            .name("Route Optimizer")
+            .description("Calculates optimal routes")
+            .tag("maps")
+            .example("Plan a route from A to B")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn agent_card() {
+        let card = AgentCard::builder()
+            .name("Route Planner")
+            .description("Plans routes")
+            .url("https://example.com/a2a")
+            .version("1.0.0")
+            .default_input_modes([MediaType::TEXT_PLAIN])
+            .default_output_modes([MediaType::TEXT_PLAIN, MediaType::APPLICATION_JSON])
+            .skill(skill())
+            .build()
+            .unwrap();
+
+        assert_eq!(card.protocol_version(), AgentCard::PROTOCOL_VERSION);
+        assert_eq!(card.skills()[0].tags(), ["maps"]);
+
+        let encoded = serde_json::to_string(&card).unwrap();
+        assert_eq!(serde_json::from_str::<AgentCard>(&encoded).unwrap(), card);
+
+        assert_eq!(
+            AgentCard::builder()
+                .name("Route Planner")
+                .description("Plans routes")
+                .url("https://example.com/a2a")
+                .version("1.0.0")
+                .default_input_modes([MediaType::TEXT_PLAIN])
+                .skill(skill())
+                .build(),
+            Err(MissingFieldError {
+                type_name: "AgentCard",
+                field: "defaultOutputModes"
+            })
+        );
+        assert_eq!(
+            AgentSkill::builder()
+                .id("a")
+                .description("b")
+                .build()
+                .unwrap_err()
+                .field,
+            "name"
+        );
+    }
+
+    #[test]
+    fn task_and_events() {
+        let artifact = Artifact::builder()
+            .artifact_id("a-1")
+            .name("route.txt")
+            .part(Part::Text(TextPart {
+                text: "Turn left".into(),
+                ..Default::default()
+            }))
+            .build()
+            .unwrap();
+
+        let task = Task::builder()
+            .id("task-1")
+            .context_id("ctx-1")
+            .status(TaskStatus::new(TaskState::Working).with_timestamp("2025-01-01T00:00:00Z"))
+            .artifact(artifact.clone())
+            .build()
+            .unwrap();
+        assert_eq!(task.kind(), "task");
+        assert_eq!(task.status().state(), &TaskState::Working);
+        assert_eq!(task.artifacts().unwrap()[0].name(), Some("route.txt"));
+
+        let submitted = Task::builder()
+            .id("task-1")
+            .context_id("ctx-1")
+            .build()
+            .unwrap();
+        assert_eq!(submitted.status().state(), &TaskState::Submitted);
+        assert_eq!(
+            Task::builder().id("task-1").build().unwrap_err().field,
+            "contextId"
+        );
+        assert!(Artifact::builder().artifact_id("a-1").build().is_err());
+
+        let event = TaskStatusUpdateEvent::builder()
+            .task_id("task-1")
+            .context_id("ctx-1")
+            .status(TaskStatus::new(TaskState::Completed))
+            .r#final(true)
+            .build()
+            .unwrap();
+        let encoded = serde_json::to_string(&event).unwrap();
+        let decoded =
+            serde_json::from_str::<SendStreamingMessageSuccessResponse>(&encoded).unwrap();
+        assert_eq!(
+            decoded,
+            SendStreamingMessageSuccessResponse::TaskStatusUpdateEvent(event)
+        );
+    }
+}
+
+#[cfg(test)]
+mod kind_sanity_checks {
+    use crate::{
+        jsonrpc::{SendMessageResult, TaskStatusUpdateEvent},
+        Message, MessageKind, Task, TaskKind,
+    };
+
+    #[test]
+    fn defaults_serialize_their_kind() {
+        let message = serde_json::to_value(Message::default()).unwrap();
+        assert_eq!(message["kind"], MessageKind::VALUE);
+
+        let task = serde_json::to_value(Task::default()).unwrap();
+        assert_eq!(task["kind"], TaskKind::VALUE);
+
+        let event = serde_json::to_value(TaskStatusUpdateEvent::default()).unwrap();
+        assert_eq!(event["kind"], "status-update");
+    }
+
+    #[test]
+    fn mismatched_kinds_are_rejected() {
+        let task_shaped_message =
+            r#"{"role": "agent", "parts": [], "messageId": "m-1", "kind": "task"}"#;
+        assert!(serde_json::from_str::<Message>(task_shaped_message).is_err());
+        assert!(serde_json::from_str::<Message>(
+            r#"{"role": "agent", "parts": [], "messageId": "m-1"}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<Task>(
+            r#"{"id": "t-1", "contextId": "c-1", "status": {"state": "working"}, "kind": "message"}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<SendMessageResult>(task_shaped_message).is_err());
+
+        assert_eq!(
+            serde_json::from_str::<MessageKind>(r#""message""#).unwrap(),
+            MessageKind
+        );
+    }
+}
+
+#[cfg(test)]
+mod task_state_sanity_checks {
+    use crate::{JsonRpcError, JsonRpcErrorCode, Task, TaskState, TaskStatus};
+
+    fn task(state: TaskState) -> Task<'static> {
+        Task::builder()
+            .id("task-1")
+            .context_id("ctx-1")
+            .status(TaskStatus::new(state))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn state_classes() {
+        let terminal = TaskState::ALL.into_iter().filter(TaskState::is_terminal);
+        assert_eq!(
+            terminal.collect::<Vec<_>>(),
+            [
+                TaskState::Completed,
+                TaskState::Canceled,
+                TaskState::Failed,
+                TaskState::Rejected
+            ]
+        );
+
+        assert!(TaskState::InputRequired.is_interrupted());
+        assert!(TaskState::AuthRequired.is_interrupted());
+        assert!(!TaskState::Working.is_interrupted());
+
+        assert!(TaskState::Working.is_cancelable());
+        assert!(!TaskState::Completed.is_cancelable());
+
+        for state in TaskState::ALL {
+            assert_eq!(
+                serde_json::to_string(&state).unwrap(),
+                format!("\"{state}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn transitions() {
+        assert!(TaskState::Submitted.can_transition_to(&TaskState::Working));
+        assert!(TaskState::Working.can_transition_to(&TaskState::Working));
+        assert!(TaskState::Working.can_transition_to(&TaskState::InputRequired));
+        assert!(TaskState::InputRequired.can_transition_to(&TaskState::Submitted));
+        assert!(TaskState::Submitted.can_transition_to(&TaskState::Rejected));
+
+        assert!(!TaskState::Completed.can_transition_to(&TaskState::Working));
+        assert!(!TaskState::Canceled.can_transition_to(&TaskState::Canceled));
+        assert!(!TaskState::Working.can_transition_to(&TaskState::Submitted));
+        assert!(!TaskState::Working.can_transition_to(&TaskState::Rejected));
+    }
+
+    #[test]
+    fn apply_status() {
+        let mut running = task(TaskState::Submitted);
+        running
+            .apply_status(TaskStatus::new(TaskState::Working))
+            .unwrap();
+        assert_eq!(running.status().state(), &TaskState::Working);
+
+        let mut done = task(TaskState::Completed);
+        let error = done
+            .apply_status(TaskStatus::new(TaskState::Working))
+            .unwrap_err();
+        assert_eq!(done.status().state(), &TaskState::Completed);
+        assert_eq!(
+            JsonRpcError::from(error).code,
+            JsonRpcErrorCode::UnsupportedOperationError
+        );
+
+        let error = done
+            .apply_status(TaskStatus::new(TaskState::Canceled))
+            .unwrap_err();
+        assert!(error.is_not_cancelable());
+        let error = JsonRpcError::from(error);
+        assert_eq!(error.code, JsonRpcErrorCode::TaskNotCancelableError);
+        assert_eq!(
+            serde_json::to_value(&error.data).unwrap(),
+            serde_json::json!({"taskId": "task-1"})
+        );
+    }
+}
+
+#[cfg(test)]
+mod reducer_sanity_checks {
+    use crate::{
+        jsonrpc::{SendStreamingMessageSuccessResponse, TaskReducer, TaskUpdateError},
+        Part, TaskState,
+    };
+
+    fn event(json: &str) -> SendStreamingMessageSuccessResponse<'_> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn reducer() -> TaskReducer<'static> {
+        let SendStreamingMessageSuccessResponse::Task(task) = event(
+            r#"{"kind": "task", "id": "task-1", "contextId": "ctx-1", "status": {"state": "submitted"}}"#,
+        ) else {
+            unreachable!()
+        };
+
+        TaskReducer::new(task)
+    }
+
+    #[test]
+    fn folds_stream() {
+        let mut reducer = reducer();
+
+        let stream = [
+            r#"{"kind": "status-update", "taskId": "task-1", "contextId": "ctx-1", "final": false,
+                "status": {"state": "working", "message": {"kind": "message", "role": "agent", "messageId": "m-1", "parts": []}}}"#,
+            r#"{"kind": "artifact-update", "taskId": "task-1", "contextId": "ctx-1", "lastChunk": false,
+                "artifact": {"artifactId": "a-1", "parts": [{"kind": "text", "text": "Turn "}]}}"#,
+            r#"{"kind": "artifact-update", "taskId": "task-1", "contextId": "ctx-1", "append": true, "lastChunk": true,
+                "artifact": {"artifactId": "a-1", "parts": [{"kind": "text", "text": "left"}]}}"#,
+            r#"{"kind": "status-update", "taskId": "task-1", "contextId": "ctx-1", "final": true,
+                "status": {"state": "completed"}}"#,
+        ];
+
+        for (index, json) in stream.into_iter().enumerate() {
+            reducer.apply(event(json)).unwrap();
+
+            if index == 1 {
+                assert_eq!(reducer.pending_artifacts().collect::<Vec<_>>(), ["a-1"]);
+            }
+        }
+
+        assert!(reducer.is_final());
+        assert_eq!(reducer.pending_artifacts().count(), 0);
+
+        let task = reducer.task();
+        assert_eq!(task.status().state(), &TaskState::Completed);
+        assert_eq!(task.history().unwrap()[0].message_id, "m-1");
+
+        let texts = task.artifacts().unwrap()[0]
+            .parts()
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => text.text.as_ref(),
+                _ => unreachable!(),
+            })
+            .collect::<String>();
+        assert_eq!(texts, "Turn left");
+
+        assert_eq!(
+            reducer.apply(event(
+                r#"{"kind": "message", "role": "user", "messageId": "m-2", "parts": []}"#
+            )),
+            Err(TaskUpdateError::AfterFinal)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_events() {
+        let mut reducer = reducer();
+
+        assert!(matches!(
+            reducer.apply(event(
+                r#"{"kind": "status-update", "taskId": "task-2", "contextId": "ctx-1", "final": false, "status": {"state": "working"}}"#
+            )),
+            Err(TaskUpdateError::TaskMismatch { .. })
+        ));
+        assert_eq!(
+            reducer.apply(event(
+                r#"{"kind": "artifact-update", "taskId": "task-1", "contextId": "ctx-1", "append": true,
+                    "artifact": {"artifactId": "a-1", "parts": []}}"#
+            )),
+            Err(TaskUpdateError::UnknownArtifact("a-1".into()))
+        );
+        assert!(reducer.task().artifacts().is_none());
+
+        reducer
+            .apply(event(
+                r#"{"kind": "status-update", "taskId": "task-1", "contextId": "ctx-1", "final": false, "status": {"state": "failed"}}"#,
+            ))
+            .unwrap();
+        assert!(matches!(
+            reducer.apply(event(
+                r#"{"kind": "status-update", "taskId": "task-1", "contextId": "ctx-1", "final": false, "status": {"state": "working"}}"#
+            )),
+            Err(TaskUpdateError::Transition(_))
+        ));
+        assert_eq!(reducer.task().status().state(), &TaskState::Failed);
+    }
+}
+
+#[cfg(test)]
+mod chunking_sanity_checks {
+    use crate::{
+        jsonrpc::{
+            ArtifactAssembler, ArtifactAssemblyError, ArtifactChunker, TaskArtifactUpdateEvent,
+            TaskReducer, TaskUpdateError,
+        },
+        Artifact, DataPart, FilePart, FileWith, FileWithBytes, MediaType, Part, Task, TextPart,
+    };
+
+    fn text(text: &str) -> Part<'_> {
+        Part::Text(TextPart {
+            text: text.into(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn round_trip() {
+        let long = "ßtreet names ".repeat(20);
+        let artifact = Artifact::builder()
+            .artifact_id("a-1")
+            .name("route.txt")
+            .part(text(&long))
+            .part(Part::Data(DataPart::default()))
+            .part(text("end"))
+            .build()
+            .unwrap();
+
+        let events = ArtifactChunker::new(16)
+            .chunk("task-1", "ctx-1", artifact.clone())
+            .unwrap();
+        assert!(events.len() > 1);
+        assert_eq!(events[0].artifact().name(), Some("route.txt"));
+        assert_eq!(events[1].artifact().name(), None);
+        assert!(events[1..].iter().all(|event| event.append() == Some(true)));
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| event.last_chunk() == Some(true))
+                .collect::<Vec<_>>(),
+            [vec![false; events.len() - 1], vec![true]].concat()
+        );
+
+        let mut assembler = ArtifactAssembler::new();
+        let mut assembled = None;
+        for event in &events {
+            assert!(assembled.is_none());
+            assembled = assembler.push(event).unwrap();
+        }
+        assert_eq!(assembled, Some(artifact));
+        assert!(assembler.finish().is_ok());
+    }
+
+    #[test]
+    fn reducer_round_trip() {
+        let long = "ßtreet names ".repeat(20);
+        let artifact = Artifact::builder()
+            .artifact_id("a-1")
+            .part(text(&long))
+            .part(Part::File(FilePart {
+                base: Default::default(),
+                file: FileWith::Bytes(FileWithBytes::new(
+                    &[7; 40],
+                    MediaType::APPLICATION_OCTET_STREAM,
+                )),
+            }))
+            .part(text("end"))
+            .build()
+            .unwrap();
+        let task = serde_json::from_str::<Task>(
+            r#"{"kind": "task", "id": "task-1", "contextId": "ctx-1", "status": {"state": "working"}}"#,
+        )
+        .unwrap();
+
+        let events = ArtifactChunker::new(16)
+            .with_split_files(true)
+            .chunk("task-1", "ctx-1", artifact.clone())
+            .unwrap();
+        assert!(events.len() > 3);
+
+        let mut reducer = TaskReducer::new(task.clone());
+        for event in events.iter().cloned() {
+            reducer.apply_artifact_update(event).unwrap();
+        }
+        assert_eq!(reducer.task().artifacts().unwrap(), [artifact]);
+
+        // The continuation of a text part with a data part.
+        let mut reducer = TaskReducer::new(task);
+        reducer.apply_artifact_update(events[0].clone()).unwrap();
+        let broken = serde_json::from_str::<TaskArtifactUpdateEvent>(
+            r#"{"kind": "artifact-update", "taskId": "task-1", "contextId": "ctx-1", "append": true,
+                "metadata": {"urn:agentic-types:artifact-chunking:continuesPart": true}, "artifact": {"artifactId": "a-1", "parts": [{"kind": "data", "data": {}}]}}"#,
+        )
+        .unwrap();
+        let before = reducer.task().clone();
+        assert_eq!(
+            reducer.apply_artifact_update(broken),
+            Err(TaskUpdateError::InvalidContinuation("a-1".into()))
+        );
+        assert_eq!(reducer.task(), &before);
+    }
+
+    #[test]
+    fn small_chunks() {
+        for bytes in ["QQ", "QUJDRA", "QUJDREVGRw"] {
+            let artifact = Artifact::builder()
+                .artifact_id("a-1")
+                .part(Part::File(FilePart {
+                    base: Default::default(),
+                    file: FileWith::Bytes(FileWithBytes::from_base64(
+                        bytes,
+                        MediaType::APPLICATION_OCTET_STREAM,
+                    )),
+                }))
+                .build()
+                .unwrap();
+
+            // File parts are only split on request.
+            let events = ArtifactChunker::new(1)
+                .chunk("task-1", "ctx-1", artifact.clone())
+                .unwrap();
+            assert_eq!(events.len(), 1, "{bytes}");
+
+            let events = ArtifactChunker::new(1)
+                .with_split_files(true)
+                .chunk("task-1", "ctx-1", artifact.clone())
+                .unwrap();
+            assert_eq!(events.len(), bytes.len().div_ceil(4), "{bytes}");
+
+            let mut assembler = ArtifactAssembler::new();
+            let assembled = events
+                .iter()
+                .map(|event| assembler.push(event).unwrap())
+                .last()
+                .flatten();
+            assert_eq!(assembled, Some(artifact), "{bytes}");
+        }
+    }
+
+    #[test]
+    fn broken_streams() {
+        let artifact = Artifact::builder()
+            .artifact_id("a-1")
+            .part(text("abcdefgh"))
+            .build()
+            .unwrap();
+        let events = ArtifactChunker::new(4)
+            .chunk("task-1", "ctx-1", artifact)
+            .unwrap();
+        assert_eq!(events.len(), 2);
+
+        let mut assembler = ArtifactAssembler::new();
+        assert_eq!(
+            assembler.push(&events[1]),
+            Err(ArtifactAssemblyError::NotStarted("a-1".into()))
+        );
+
+        assembler.push(&events[0]).unwrap();
+        assert_eq!(
+            assembler.push(&events[0]),
+            Err(ArtifactAssemblyError::AlreadyStarted("a-1".into()))
+        );
+        assert_eq!(assembler.pending().collect::<Vec<_>>(), ["a-1"]);
+        assert_eq!(
+            assembler.clone().finish(),
+            Err(ArtifactAssemblyError::Incomplete(vec!["a-1".into()]))
+        );
+
+        assert!(assembler.push(&events[1]).unwrap().is_some());
+        assert_eq!(
+            assembler.push(&events[1]),
+            Err(ArtifactAssemblyError::AlreadyComplete("a-1".into()))
+        );
+    }
+}
+
+#[cfg(test)]
+mod media_type_sanity_checks {
+    use crate::{
+        jsonrpc::MessageSendConfiguration, negotiate_output_modes, AgentCard, AgentSkill,
+        JsonRpcErrorCode, MediaType,
+    };
+
+    fn media_type(media_type: &str) -> MediaType<'_> {
+        MediaType::parse(media_type).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let html = media_type(" text/html ; charset=\"utf-8\"; q=0.5 ");
+        assert_eq!(html.as_str(), "text/html ; charset=\"utf-8\"; q=0.5");
+        assert_eq!(html.essence(), "text/html");
+        assert_eq!(html.main_type(), "text");
+        assert_eq!(html.subtype(), "html");
+        assert_eq!(html.parameter("Charset"), Some("utf-8"));
+        assert_eq!(html.parameters().count(), 2);
+
+        assert!(media_type("image/*").is_wildcard());
+        for invalid in [
+            "text",
+            "text/",
+            "*/plain",
+            "text/plain; charset",
+            "te xt/plain",
+        ] {
+            assert!(MediaType::parse(invalid).is_err(), "{invalid}");
+        }
+
+        let configuration = serde_json::from_str::<MessageSendConfiguration>(
+            r#"{"acceptedOutputModes": ["text/plain", "image/*"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            configuration.accepted_output_modes().unwrap(),
+            ["text/plain", "image/*"]
+        );
+
+        // Invalid media types are kept as written.
+        let configuration = serde_json::from_str::<MessageSendConfiguration>(
+            r#"{"acceptedOutputModes": ["text", "*/plain"]}"#,
+        )
+        .unwrap();
+        let modes = configuration.accepted_output_modes().unwrap();
+        assert_eq!(modes, ["text", "*/plain"]);
+        assert!(!modes[0].is_valid());
+        assert_eq!(
+            modes[0].validate().unwrap_err().to_string(),
+            "`text` is not a valid media type, the `/` is missing"
+        );
+        assert_eq!(modes[0].essence(), "text");
+        assert_eq!(modes[0].subtype(), "");
+        assert!(!modes[1].is_wildcard());
+        assert!(modes[0].matches(&MediaType::lenient("TEXT")));
+        assert!(!MediaType::ANY.matches(&modes[0]));
+        assert_eq!(MediaType::lenient(" text/plain "), MediaType::TEXT_PLAIN);
+    }
+
+    #[test]
+    fn matching() {
+        assert!(media_type("image/*").matches(&MediaType::IMAGE_PNG));
+        assert!(MediaType::ANY.matches(&MediaType::APPLICATION_JSON));
+        assert!(media_type("TEXT/Plain").matches(&MediaType::TEXT_PLAIN));
+        assert!(media_type("text/plain; charset=utf-8").matches(&MediaType::TEXT_PLAIN));
+        assert!(!media_type("text/plain; charset=utf-8")
+            .matches(&media_type("text/plain; charset=latin1")));
+        assert!(!MediaType::TEXT_PLAIN.matches(&MediaType::APPLICATION_JSON));
+
+        // Parameter values are case-sensitive, except for `charset`.
+        assert!(media_type("text/plain; charset=UTF-8")
+            .matches(&media_type("text/plain; charset=\"utf-8\"")));
+        assert!(!media_type("text/plain; format=Flowed")
+            .matches(&media_type("text/plain; format=flowed")));
+    }
+
+    #[test]
+    fn equality() {
+        use std::collections::HashSet;
+
+        assert_eq!(media_type("Text/Plain"), MediaType::TEXT_PLAIN);
+        assert_eq!(
+            media_type("text/html;Charset=UTF-8"),
+            media_type("TEXT/HTML ; charset=\"utf-8\"")
+        );
+        assert_ne!(
+            media_type("text/plain; format=Flowed"),
+            media_type("text/plain; format=flowed")
+        );
+        assert_eq!(MediaType::lenient("TEXT"), MediaType::lenient("text"));
+        assert_ne!(MediaType::lenient("text"), MediaType::TEXT_PLAIN);
+
+        let modes = HashSet::from([media_type("Text/Plain"), MediaType::TEXT_PLAIN]);
+        assert_eq!(modes.len(), 1);
+        assert!(media_type("TEXT/PLAIN") > MediaType::TEXT_MARKDOWN);
+        assert_eq!(
+            negotiate_output_modes(
+                &[media_type("Text/Plain"), MediaType::TEXT_PLAIN],
+                &[MediaType::TEXT_PLAIN]
+            )
+            .unwrap(),
+            [MediaType::TEXT_PLAIN]
+        );
+    }
+
+    #[test]
+    fn negotiation() {
+        let offered = [MediaType::APPLICATION_JSON, media_type("image/*")];
+
+        assert_eq!(
+            negotiate_output_modes(
+                &[MediaType::IMAGE_PNG, MediaType::APPLICATION_JSON],
+                &offered
+            )
+            .unwrap(),
+            [MediaType::IMAGE_PNG, MediaType::APPLICATION_JSON]
+        );
+        assert_eq!(negotiate_output_modes(&[], &offered).unwrap(), offered);
+
+        let error = negotiate_output_modes(&[MediaType::TEXT_PLAIN], &offered).unwrap_err();
+        assert_eq!(error.code, JsonRpcErrorCode::ContentTypeNotSupportedError);
+
+        let skill = AgentSkill::builder()
+            .id("render")
+            .name("Render")
+            .description("Renders charts")
+            .output_modes([MediaType::IMAGE_PNG])
+            .build()
+            .unwrap();
+        let card = AgentCard::builder()
+            .name("Charts")
+            .description("Draws charts")
+            .url("https://example.com/a2a")
+            .version("1.0.0")
+            .default_input_modes([MediaType::TEXT_PLAIN])
+            .default_output_modes([MediaType::TEXT_PLAIN])
+            .skill(skill.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(card.output_modes_of(None), [MediaType::TEXT_PLAIN]);
+        assert_eq!(card.output_modes_of(Some(&skill)), [MediaType::IMAGE_PNG]);
+        assert!(card
+            .negotiate_output_modes(Some(&skill), &[MediaType::TEXT_PLAIN])
+            .is_err());
+    }
+}
+
+#[cfg(test)]
+mod file_bytes_sanity_checks {
+    use crate::{
+        FileBytesError, FileDecoder, FileWithBytes, JsonRpcError, JsonRpcErrorCode, MediaType,
+    };
+
+    #[test]
+    fn round_trip() {
+        let content = (0..=255u8).cycle().take(10_000).collect::<Vec<_>>();
+
+        for length in [0, 1, 2, 3, 4, 5, 3071, 3072, 3073, 10_000] {
+            let file = FileWithBytes::new(&content[..length], MediaType::APPLICATION_OCTET_STREAM);
+            assert_eq!(file.bytes().len(), length.div_ceil(3) * 4);
+            assert_eq!(file.decode().unwrap(), &content[..length]);
+
+            let mut written = Vec::new();
+            assert_eq!(
+                FileDecoder::new().decode_to(&file, &mut written).unwrap(),
+                length as u64
+            );
+            assert_eq!(written, &content[..length]);
+        }
+
+        let file = FileWithBytes::new(b"%PDF-1.7", MediaType::APPLICATION_PDF).with_name("a.pdf");
+        assert_eq!(file.bytes(), "JVBERi0xLjc=");
+        assert_eq!(
+            serde_json::to_value(&file).unwrap(),
+            serde_json::json!({"name": "a.pdf", "mimeType": "application/pdf", "bytes": "JVBERi0xLjc="})
+        );
+        assert_eq!(
+            FileWithBytes::from_base64("JVBERi0xLjc", MediaType::APPLICATION_PDF)
+                .decode()
+                .unwrap(),
+            b"%PDF-1.7"
+        );
+    }
+
+    #[test]
+    fn invalid_payloads() {
+        for (encoded, offset) in [("ab$d", 2), ("ab=d", 2), ("abcde", 4), ("a===", 1)] {
+            let file = FileWithBytes::from_base64(encoded, MediaType::APPLICATION_OCTET_STREAM);
+            assert!(
+                matches!(file.decode(), Err(FileBytesError::InvalidBase64 { offset: found }) if found == offset),
+                "{encoded}"
+            );
+        }
+
+        let file = FileWithBytes::new(&[0; 1000], MediaType::APPLICATION_OCTET_STREAM);
+        let mut written = Vec::new();
+        let error = FileDecoder::new()
+            .with_max_size(999)
+            .decode_to(&file, &mut written)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            FileBytesError::TooLarge {
+                size: 1000,
+                max_size: 999
+            }
+        ));
+        assert!(written.is_empty());
+        assert_eq!(
+            JsonRpcError::from(error).code,
+            JsonRpcErrorCode::InvalidParams
+        );
+    }
+}
+
+#[cfg(test)]
+mod file_with_sanity_checks {
+    use std::fs;
+
+    use crate::{
+        FetchedFile, FileFetchError, FileFetcher, FileWith, FileWithUri, LocalFileFetcher,
+        MediaType,
+    };
+
+    #[test]
+    fn exactly_one_of_bytes_and_uri() {
+        let bytes =
+            serde_json::from_str::<FileWith>(r#"{"name": "a.txt", "bytes": "YQ=="}"#).unwrap();
+        assert!(matches!(bytes, FileWith::Bytes(_)));
+        assert_eq!(bytes.name(), Some("a.txt"));
+
+        let uri = serde_json::from_str::<FileWith>(
+            r#"{"mimeType": "image/png", "uri": "https://example.com/a.png"}"#,
+        )
+        .unwrap();
+        assert!(matches!(&uri, FileWith::Uri(file) if file.uri() == "https://example.com/a.png"));
+        assert_eq!(uri.mime_type(), Some(&MediaType::IMAGE_PNG));
+
+        let both =
+            serde_json::from_str::<FileWith>(r#"{"bytes": "YQ==", "uri": "data:,a"}"#).unwrap_err();
+        assert!(both.to_string().contains("not both"), "{both}");
+
+        let neither = serde_json::from_str::<FileWith>(r#"{"name": "a.txt"}"#).unwrap_err();
+        assert!(
+            neither.to_string().contains("`bytes` or `uri`"),
+            "{neither}"
+        );
+    }
+
+    #[test]
+    fn data_uris() {
+        let fetcher = LocalFileFetcher::new();
+
+        let file = FileWith::Uri(FileWithUri::new(
+            "data:text/plain;base64,aGVsbG8=",
+            MediaType::TEXT_PLAIN,
+        ))
+        .resolve(&fetcher)
+        .unwrap();
+        assert_eq!(file.decode().unwrap(), b"hello");
+        assert_eq!(file.mime_type(), Some(&MediaType::TEXT_PLAIN));
+
+        assert_eq!(
+            fetcher.fetch("data:application/json,%7B%7D").unwrap(),
+            FetchedFile {
+                content: b"{}".to_vec(),
+                mime_type: Some(MediaType::APPLICATION_JSON),
+            }
+        );
+        assert_eq!(
+            fetcher
+                .fetch("data:application/octet-stream,%FF%00a")
+                .unwrap()
+                .content,
+            [0xff, 0, b'a']
+        );
+        assert!(matches!(
+            fetcher.fetch("data:text/pl%FFain,a"),
+            Err(FileFetchError::InvalidUri { .. })
+        ));
+        assert!(matches!(
+            LocalFileFetcher::new()
+                .with_max_size(4)
+                .fetch("data:;base64,aGVsbG8="),
+            Err(FileFetchError::Bytes(_))
+        ));
+        assert!(matches!(
+            fetcher.fetch("https://example.com/a.png"),
+            Err(FileFetchError::UnsupportedScheme(_))
+        ));
+        assert!(matches!(
+            fetcher.fetch("file:///etc/hostname"),
+            Err(FileFetchError::UnsupportedScheme(_))
+        ));
+    }
+
+    #[test]
+    fn file_uris() {
+        let root = std::env::temp_dir().join(format!("agentic-types-{}", std::process::id()));
+        fs::create_dir_all(root.join("files")).unwrap();
+        fs::write(root.join("files/report.txt"), "report").unwrap();
+        fs::write(root.join("secret.txt"), "secret").unwrap();
+
+        let fetcher = LocalFileFetcher::new().with_root(root.join("files"));
+        let uri = |path: &str| format!("file://{}", root.join(path).display());
+
+        let file = FileWithUri::new(uri("files/report.txt"), MediaType::TEXT_PLAIN)
+            .with_name("report.txt")
+            .resolve(&fetcher)
+            .unwrap();
+        assert_eq!(file.decode().unwrap(), b"report");
+        assert_eq!(file.name(), Some("report.txt"));
+
+        assert!(matches!(
+            fetcher.fetch(&uri("files/../secret.txt")),
+            Err(FileFetchError::NotAllowed(_))
+        ));
+        assert!(matches!(
+            fetcher.fetch(&uri("files/missing.txt")),
+            Err(FileFetchError::Io(_))
+        ));
+        // Whether a file exists outside of the root is not revealed.
+        for outside in [
+            "missing.txt",
+            "files/../missing.txt",
+            "files/../../../etc/nope",
+        ] {
+            assert!(
+                matches!(
+                    fetcher.fetch(&uri(outside)),
+                    Err(FileFetchError::NotAllowed(_))
+                ),
+                "{outside}"
+            );
+        }
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(root.join("secret.txt"), root.join("files/link.txt"))
+                .unwrap();
+            assert!(matches!(
+                fetcher.fetch(&uri("files/link.txt")),
+                Err(FileFetchError::NotAllowed(_))
+            ));
+        }
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod projection_sanity_checks {
+    use crate::{
+        jsonrpc::{ListTasksParams, MessageSendConfiguration, TaskQueryParams},
+        Artifact, JsonRpcErrorCode, Message, Part, Task, TaskProjection,
+    };
+
+    fn task() -> Task<'static> {
+        let message = |id: &'static str| Message {
+            message_id: id.into(),
+            ..Message::new()
+        };
+
+        Task::builder()
+            .id("task-1")
+            .context_id("ctx-1")
+            .message(message("m-1"))
+            .message(message("m-2"))
+            .message(message("m-3"))
+            .artifact(
+                Artifact::builder()
+                    .artifact_id("a-1")
+                    .part(Part::default())
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn history(task: &Task) -> Vec<String> {
+        task.history()
+            .unwrap_or_default()
+            .iter()
+            .map(|message| message.message_id.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn project() {
+        assert_eq!(task().project(TaskProjection::ALL), task());
+
+        let projected = task().project(
+            TaskQueryParams::new("task-1")
+                .with_history_length(2)
+                .projection()
+                .unwrap(),
+        );
+        assert_eq!(history(&projected), ["m-2", "m-3"]);
+        assert!(projected.artifacts().is_some());
+
+        let projected = task().project(TaskProjection::new(Some(10), true).unwrap());
+        assert_eq!(history(&projected), ["m-1", "m-2", "m-3"]);
+
+        let listed = task().project(ListTasksParams::new().projection().unwrap());
+        assert!(history(&listed).is_empty());
+        assert!(listed.artifacts().is_none());
+        let encoded = serde_json::to_value(&listed).unwrap();
+        assert!(encoded.get("artifacts").is_none());
+
+        let listed = task().project(
+            ListTasksParams::new()
+                .with_history_length(1)
+                .with_include_artifacts(true)
+                .projection()
+                .unwrap(),
+        );
+        assert_eq!(history(&listed), ["m-3"]);
+        assert_eq!(listed.artifacts().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn negative_history_length() {
+        for error in [
+            TaskQueryParams::new("task-1")
+                .with_history_length(-1)
+                .projection(),
+            ListTasksParams::new().with_history_length(-5).projection(),
+            MessageSendConfiguration::new()
+                .with_history_length(-1)
+                .projection(),
+        ] {
+            assert_eq!(error.unwrap_err().code, JsonRpcErrorCode::InvalidParams);
+        }
+    }
+}
+
+#[cfg(test)]
+mod card_validation_sanity_checks {
+    use crate::{AgentCard, CardIssue, CardValidator, Severity, TransportProtocol};
+
+    const CARD: &str = r#"
+{
+  "protocolVersion": "0.3.0",
+  "name": "Route Planner",
+  "description": "Plans routes",
+  "url": "https://example.com/a2a",
+  "preferredTransport": "JSONRPC",
+  "additionalInterfaces": [
+    {"url": "https://example.com/a2a", "transport": "JSONRPC"},
+    {"url": "https://example.com/grpc", "transport": "GRPC"}
+  ],
+  "version": "1.0.0",
+  "capabilities": {"streaming": false, "pushNotifications": false},
+  "securitySchemes": {
+    "oauth/2": {"type": "http", "scheme": "bearer"}
+  },
+  "security": [{"oauth/2": []}],
+  "defaultInputModes": ["text/plain"],
+  "defaultOutputModes": ["text/plain"],
+  "skills": [
+    {"id": "route", "name": "Route", "description": "Routes", "tags": [], "security": [{"oauth/2": ["read"]}]},
+    {"id": "map", "name": "Map", "description": "Maps", "tags": []}
+  ]
+}"#;
+
+    fn card(json: &str) -> AgentCard<'_> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn valid_card() {
+        assert_eq!(card(CARD).validate(), []);
+    }
+
+    #[test]
+    fn urls() {
+        let json = CARD.replace("https://example.com/grpc", "/grpc").replace(
+            "\"url\": \"https://example.com/a2a\",\n",
+            "\"url\": \"http://localhost:8080/a2a\",\n",
+        );
+        let card = card(&json);
+        let diagnostics = card.validate();
+
+        let paths = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                "/url",
+                "/additionalInterfaces/1/url",
+                "/additionalInterfaces"
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].issue,
+            CardIssue::InsecureUrl("http://localhost:8080/a2a".into())
+        );
+        assert!(matches!(diagnostics[1].issue, CardIssue::InvalidUrl { .. }));
+        assert_eq!(
+            diagnostics[2].issue,
+            CardIssue::PreferredInterfaceMissing {
+                url: "http://localhost:8080/a2a".into(),
+                transport: TransportProtocol::JsonRpc
+            }
+        );
+        assert_eq!(diagnostics[2].severity(), Severity::Warning);
+
+        let dev = CardValidator::new().with_dev_mode(true).validate(&card);
+        assert_eq!(dev.len(), 2);
+        assert!(!dev.iter().any(|diagnostic| diagnostic.path == "/url"));
+
+        for url in [
+            "https://",
+            "ftp://example.com",
+            "https://exa mple.com",
+            "example.com",
+        ] {
+            let json = CARD.replace("https://example.com/grpc", url);
+            assert!(
+                matches!(
+                    self::card(&json).validate()[0].issue,
+                    CardIssue::InvalidUrl { .. }
+                ),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn skills_and_security() {
+        let json = CARD
+            .replace("\"id\": \"map\"", "\"id\": \"route\"")
+            .replace(
+                "[{\"oauth/2\": [\"read\"]}]",
+                "[{\"oauth/2\": [], \"mtls\": []}]",
+            )
+            .replace(
+                "\"security\": [{\"oauth/2\": []}]",
+                "\"security\": [{\"api~key\": []}]",
+            );
+        let diagnostics = card(&json).validate();
+
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.path.as_str(), &diagnostic.issue))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    "/security/0/api~0key",
+                    &CardIssue::UnknownSecurityScheme("api~key".into())
+                ),
+                (
+                    "/skills/0/security/0/mtls",
+                    &CardIssue::UnknownSecurityScheme("mtls".into())
+                ),
+                ("/skills/1/id", &CardIssue::DuplicateSkillId("route".into())),
+            ]
+        );
+        assert!(diagnostics.iter().all(|diagnostic| diagnostic.is_error()));
+    }
+
+    #[test]
+    fn modes_and_version() {
+        let json = CARD.replace("\"0.3.0\"", "\"9.9.9\"").replace(
+            "\"defaultOutputModes\": [\"text/plain\"]",
+            "\"defaultOutputModes\": []",
+        );
+        let diagnostics = card(&json).validate();
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "warning at `/protocolVersion`: `9.9.9` is not a known protocol version"
+        );
+        assert_eq!(diagnostics[1].path, "/defaultOutputModes");
+        assert_eq!(diagnostics[1].issue, CardIssue::NoModes);
+
+        let json = CARD
+            .replace(
+                "\"defaultInputModes\": [\"text/plain\"]",
+                "\"defaultInputModes\": [\"text/plain\", \"text\"]",
+            )
+            .replace(
+                "\"tags\": []}\n",
+                "\"tags\": [], \"inputModes\": [\"image/png\"], \"outputModes\": [\"*/png\"]}\n",
+            );
+        let diagnostics = card(&json).validate();
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "error at `/defaultInputModes/1`: `text` is not a valid media type, the `/` is missing"
+        );
+        assert_eq!(diagnostics[1].path, "/skills/1/outputModes/0");
+        assert_eq!(
+            diagnostics[1].issue,
+            CardIssue::InvalidMediaType {
+                value: "*/png".into(),
+                reason: "a wildcard type requires a wildcard subtype",
+            }
+        );
+    }
+}
+
+#[cfg(all(test, feature = "jws"))]
+mod jws_sanity_checks {
+    use ring::{
+        rand::SystemRandom,
+        signature::{EcdsaKeyPair, Ed25519KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+    };
+
+    use crate::{
+        encode, to_canonical_string, to_canonical_vec, verify_card_signatures, AgentCard,
+        AgentSkill, CardSignatureError, FetchedFile, FileDecoder, FileFetchError, FileFetcher,
+        JkuResolver, JwkSet, JwsAlgorithm, JwsHeader, KeyResolver, MediaType, SigningKey,
+    };
+
+    /// A 2048-bit RSA key in PKCS#8 DER, ring cannot generate RSA keys.
+    const RSA_PKCS8: &str = concat!(
+        "MIIEuwIBADANBgkqhkiG9w0BAQEFAASCBKUwggShAgEAAoIBAQCnEfFo4ekgEHDYTMnruxIuP1SaYVf+D2aqtGIk",
+        "RoO5T7QQrZ+vg4QSbshHblGHHS4tQ4F3Ku1GpTaW2OQorzRc+gEOUb3NYFPjyHTDQLVEebLytUOB3gBIvKtM/HG3",
+        "CB/EGFbS1n6cdql4CbBnTU4VaJc3IF9/aIPaHYbXn4+o30Yj+oF/YAZccmk87pw9rFQM8gOECyoGOISqkQoaEc0z",
+        "Tq+pqJjTcHy/G1NGwmb4ifQRdXNw+AUdVk7ri+zAmHEQO3yNCFJe4G52vIJaNXbi8JsSlVfxalmN15xhFsxQ/nZE",
+        "UzXNqhAFWR35XeD4LcoGPx1W1g0ES8UHc90iBxxzAgMBAAECgf9DHByyL2JK/vpVPdiJAxwfzPtZssB7IrSa3YU5",
+        "Ym8x1lJTthVEQLqiAjNxKLSXEaGljPhJu3SUbB5Wzx4sjt75z3LyeDibHEFBE8R9/1V7rBG1whoxOyaMu+do2ql9",
+        "sZsQ/2CLpfLgkXSBmLgYxsoWz2yZOD0kG1eGG1Y5nF6YoRvNTxbgQKowhDzOcZKLJn0Joq76HbIJ5XBKx+kOLfpv",
+        "WZACTFs9Ou0xMc8xNSVDWglPmTmLOxZrSOjipKrqBiDpQPbdaDOvxYgP/qc219o2MtEglE4fEui0l9ya55c57E6j",
+        "lWEs1JAfHy4ow4ZohQc0dc+WQFi8TyL698I4UjUCgYEA1fjhcLkvx20gvfbRD1EOAdLKhlvA4Diokn/9jTSwYICC",
+        "vBGjpuMb32/21QQtGl2LqyhFnXTMZcv0zYnrRhXL5STXPkUUdqrD1g+SQO4EvgEeyLlPt5eRRZhW10k3A8TBHfpO",
+        "AxTZX4cf3KimtxfkJtmKGd5Fnb8Xahvn+wlSHVUCgYEAx+Kxm7lOBJOWT5q4nSM/Ohv44h2WM9e07faUpLhYoLEm",
+        "aIkDnt0HcYWb+9hS5wZ2R0Li/QwMLUJYzbdh3h9Ts39kjaYK7U/E68xWh2/vABwyAn9Jh18gyCow8JSDmcB76cSw",
+        "7Mm4CCi0+hSxT56e2jaxp6iJ9iYdBfj6e/nOEqcCgYBwK0xjf3EgpoCnlR/7W45wPkSVMrxlzs3wHtCYmapsrHHc",
+        "jZKxBhWyiEtpWAsc6zUwwKfd50DPjKAEr+x6jSqaMTn1Ftmna9XF/du51gz+Xi/5Icw8fmRVi4rayTLh8t8aDAGh",
+        "c+iQP5HgZzkeepPMGfZfoTn1fBuWMJLn8vbtEQKBgQCfTLWLi8T3tL+INaCH6z1KjFxvqHC6ZGDXfTq9hAVuG4Cm",
+        "svtlsnVqLbdAoQiBAg9lf5LzZJSXxVaMrGec5I6KcS6sDPaMZpRj7/fN/JEXH2FjyBnLfy7bF3TlZxbyhAq9JXpz",
+        "W9gE+Jwy6ibdc2baNdij65f3yiw7RFAsWcu35QKBgEFgtCXyIz8pbDsXHuQ5ujgmnqBdmPnMCO8CNAPsirCkL7Mn",
+        "dNm5z4ijxhp8huN0Zlna4WTJZY/OIlsYM3FI6lhGO2NuQeXI14DmO5TRLv/QPQ/Dv1K5cwUwdwYy9R6MKR5t9UzD",
+        "KZCf0053SVjVJnb/bKYc82njxsHglpSLPlo8",
+    );
+
+    fn card() -> AgentCard<'static> {
+        AgentCard::builder()
+            .name("Route Planner")
+            .description("Plans routes")
+            .url("https://example.com/a2a")
+            .version("1.0.0")
+            .default_input_modes([MediaType::TEXT_PLAIN])
+            .default_output_modes([MediaType::TEXT_PLAIN])
+            .skill(
+                AgentSkill::builder()
+                    .id("route")
+                    .name("Route")
+                    .description("Routes")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn verify(
+        card: &AgentCard<'_>,
+        resolver: &impl KeyResolver,
+    ) -> Result<JwsHeader, CardSignatureError> {
+        verify_card_signatures(&serde_json::to_vec(card).unwrap(), resolver)
+    }
+
+    fn key(algorithm: JwsAlgorithm) -> SigningKey {
+        let random = SystemRandom::new();
+        let pkcs8 = match algorithm {
+            JwsAlgorithm::Es256 => {
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &random)
+                    .unwrap()
+                    .as_ref()
+                    .to_vec()
+            }
+            JwsAlgorithm::EdDsa => Ed25519KeyPair::generate_pkcs8(&random)
+                .unwrap()
+                .as_ref()
+                .to_vec(),
+            JwsAlgorithm::Rs256 => FileDecoder::new().decode_base64(RSA_PKCS8).unwrap(),
+        };
+
+        SigningKey::from_pkcs8(algorithm, &pkcs8)
+            .unwrap()
+            .with_key_id(algorithm.as_str())
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        for algorithm in JwsAlgorithm::ALL {
+            let key = key(algorithm);
+            let keys = JwkSet::new().with_key(key.public_jwk());
+
+            let mut card = card();
+            card.sign(&key).unwrap();
+
+            // The signature survives a round trip, whatever the order of the members.
+            let json = serde_json::to_string(&card).unwrap();
+            let card = serde_json::from_str::<AgentCard>(&json).unwrap();
+
+            let header = verify(&card, &keys).unwrap();
+            assert_eq!(header.alg, algorithm.as_str());
+            assert_eq!(header.kid.as_deref(), Some(algorithm.as_str()));
+            assert_eq!(header.typ.as_deref(), Some("JOSE"));
+
+            let tampered = json.replace("Plans routes", "Plans detours");
+            assert!(matches!(
+                verify_card_signatures(tampered.as_bytes(), &keys),
+                Err(CardSignatureError::Mismatch)
+            ));
+        }
+    }
+
+    #[test]
+    fn keys() {
+        let key = key(JwsAlgorithm::EdDsa);
+        let mut card = card();
+
+        assert!(matches!(
+            verify(&card, &JwkSet::new()),
+            Err(CardSignatureError::Unsigned)
+        ));
+        assert!(SigningKey::from_pkcs8(JwsAlgorithm::Es256, b"not a key").is_err());
+
+        card.sign(&key).unwrap();
+
+        let mut other = key.public_jwk();
+        other.kid = Some("other".into());
+        assert!(matches!(
+            verify(&card, &JwkSet::new().with_key(other)),
+            Err(CardSignatureError::KeyNotFound(Some(kid))) if kid == "EdDSA"
+        ));
+
+        // A second signature made with a key the verifier knows is enough.
+        let second = self::key(JwsAlgorithm::Es256);
+        card.sign(&second).unwrap();
+        assert_eq!(card.signatures().unwrap().len(), 2);
+        assert_eq!(
+            verify(&card, &JwkSet::new().with_key(second.public_jwk()))
+                .unwrap()
+                .alg,
+            "ES256"
+        );
+    }
+
+    /// A card signed by another implementation, with members [AgentCard] does not model.
+    #[test]
+    fn received_json() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let key = SigningKey::from_pkcs8(JwsAlgorithm::EdDsa, pkcs8.as_ref())
+            .unwrap()
+            .with_key_id("partner");
+        let keys = JwkSet::new().with_key(key.public_jwk());
+
+        let mut card = serde_json::to_value(card()).unwrap();
+        card["iconUrl"] = serde_json::Value::Null;
+        card["x-partner"] = serde_json::json!({"tier": "gold", "regions": ["eu", "us"]});
+
+        let encode_url = |bytes: &[u8]| {
+            encode(bytes)
+                .trim_end_matches('=')
+                .replace('+', "-")
+                .replace('/', "_")
+        };
+        let protected = encode_url(br#"{"alg":"EdDSA","kid":"partner"}"#);
+        let payload = encode_url(&to_canonical_vec(&card).unwrap());
+        let signature = encode_url(
+            pair.sign(format!("{protected}.{payload}").as_bytes())
+                .as_ref(),
+        );
+        card["signatures"] = serde_json::json!([{"protected": protected, "signature": signature}]);
+
+        let json = serde_json::to_vec(&card).unwrap();
+        assert_eq!(
+            verify_card_signatures(&json, &keys).unwrap().kid.as_deref(),
+            Some("partner")
+        );
+
+        // The typed card drops the members it does not model.
+        let typed = serde_json::from_slice::<AgentCard>(&json).unwrap();
+        card.as_object_mut().unwrap().remove("signatures");
+        assert_ne!(
+            typed.signing_payload().unwrap(),
+            to_canonical_string(&card).unwrap().into_bytes()
+        );
+        assert!(matches!(
+            verify(&typed, &keys),
+            Err(CardSignatureError::Mismatch)
+        ));
+
+        assert!(matches!(
+            verify_card_signatures(br#"{"name": "Planner", "signatures": null}"#, &keys),
+            Err(CardSignatureError::Unsigned)
+        ));
+        assert!(matches!(
+            verify_card_signatures(b"[]", &keys),
+            Err(CardSignatureError::Json(_))
+        ));
+    }
+
+    struct KeySetFetcher(JwkSet);
+
+    impl FileFetcher for KeySetFetcher {
+        fn fetch(&self, uri: &str) -> Result<FetchedFile, FileFetchError> {
+            match uri {
+                "https://example.com/.well-known/jwks.json" => Ok(FetchedFile {
+                    content: serde_json::to_vec(&self.0).unwrap(),
+                    mime_type: Some(MediaType::APPLICATION_JSON),
+                }),
+                _ => Err(FileFetchError::NotAllowed(uri.to_owned())),
+            }
+        }
+    }
+
+    #[test]
+    fn jku() {
+        let key =
+            key(JwsAlgorithm::Es256).with_jwk_set_url("https://example.com/.well-known/jwks.json");
+        let fetcher = KeySetFetcher(JwkSet::new().with_key(key.public_jwk()));
+
+        let mut card = card();
+        card.sign(&key).unwrap();
+
+        let resolver = JkuResolver::new(fetcher).with_trusted_prefix("https://example.com/");
+        assert_eq!(
+            verify(&card, &resolver).unwrap().jku.as_deref(),
+            key.jwk_set_url()
+        );
+
+        let resolver = JkuResolver::new(KeySetFetcher(JwkSet::new()))
+            .with_trusted_prefix("https://example.org/");
+        assert!(matches!(
+            verify(&card, &resolver),
+            Err(CardSignatureError::UntrustedJwkSetUrl(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod canonical_sanity_checks {
+    use crate::{to_canonical_string, to_canonical_vec, Message};
+
+    /// RFC 8785 appendix B, the IEEE 754 bits of a double and its canonical form.
+    const NUMBERS: &[(u64, &str)] = &[
+        (0x0000000000000000, "0"),
+        (0x8000000000000000, "0"),
+        (0x0000000000000001, "5e-324"),
+        (0x8000000000000001, "-5e-324"),
+        (0x7fefffffffffffff, "1.7976931348623157e+308"),
+        (0xffefffffffffffff, "-1.7976931348623157e+308"),
+        (0x4340000000000000, "9007199254740992"),
+        (0xc340000000000000, "-9007199254740992"),
+        (0x4430000000000000, "295147905179352830000"),
+        (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+        (0x44b52d02c7e14af6, "1e+23"),
+        (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+        (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+        (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+        (0x444b1ae4d6e2ef50, "1e+21"),
+        (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+        (0x3eb0c6f7a0b5ed8d, "0.000001"),
+        (0x41b3de4355555553, "333333333.3333332"),
+        (0x41b3de4355555554, "333333333.33333325"),
+        (0x41b3de4355555555, "333333333.3333333"),
+        (0x41b3de4355555556, "333333333.3333334"),
+        (0x41b3de4355555557, "333333333.33333343"),
+        (0xbecbf647612f3696, "-0.0000033333333333333333"),
+        (0x43143ff3c1cb0959, "1424953923781206.2"),
+    ];
+
+    #[test]
+    fn numbers() {
+        for (bits, expected) in NUMBERS {
+            assert_eq!(
+                to_canonical_string(&f64::from_bits(*bits)).unwrap(),
+                *expected,
+                "{bits:#018x}"
+            );
+        }
+
+        assert_eq!(
+            to_canonical_string(&[1u64, 100, 1 << 60]).unwrap(),
+            "[1,100,1152921504606847000]"
+        );
+        assert_eq!(to_canonical_string(&(-42i32)).unwrap(), "-42");
+    }
+
+    /// RFC 8785 section 3.2.2.
+    #[test]
+    fn whitespace_and_strings() {
+        let input = r#"{
+  "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+  "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+  "literals": [null, true, false]
+}"#;
+        let value = serde_json::from_str::<serde_json::Value>(input).unwrap();
+
+        assert_eq!(
+            to_canonical_string(&value).unwrap(),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+    }
+
+    /// RFC 8785 section 3.2.3.
+    #[test]
+    fn member_order() {
+        let input = r#"{
+  "\u20ac": "Euro Sign",
+  "\r": "Carriage Return",
+  "\ufb33": "Hebrew Letter Dalet With Dagesh",
+  "1": "One",
+  "\ud83d\ude00": "Emoji: Grinning Face",
+  "\u0080": "Control",
+  "\u00f6": "Latin Small Letter O With Diaeresis"
+}"#;
+        let value = serde_json::from_str::<serde_json::Value>(input).unwrap();
+
+        assert_eq!(
+            to_canonical_string(&value).unwrap(),
+            "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",\
+             \"ö\":\"Latin Small Letter O With Diaeresis\",\"€\":\"Euro Sign\",\
+             \"😀\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}"
+        );
+    }
+
+    #[test]
+    fn messages() {
+        let first = r#"{"kind": "message", "messageId": "1", "role": "user",
+            "parts": [{"kind": "text", "text": "hi"}], "metadata": {"b": 1.0, "a": 2}}"#;
+        let second = r#"{"role":"user","metadata":{"a":2,"b":1},"parts":[{"text":"hi","kind":"text"}],
+            "messageId":"1","kind":"message"}"#;
+
+        let first = serde_json::from_str::<Message>(first).unwrap();
+        let second = serde_json::from_str::<Message>(second).unwrap();
+
+        assert_eq!(
+            to_canonical_vec(&first).unwrap(),
+            to_canonical_vec(&second).unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod discovery_sanity_checks {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use crate::{
+        AgentCard, AgentSkill, CardDiscovery, DiscoveryError, JsonRpcErrorCode, MediaType,
+        PlainHttpClient, LEGACY_AGENT_CARD_PATH,
+    };
+
+    /// The request line with the headers, and the body of every request received.
+    type Requests = Arc<Mutex<Vec<(String, String)>>>;
+
+    /// Answer every request on a local port with `respond(head, body)`.
+    fn serve(respond: impl Fn(&str, &str) -> String + Send + 'static) -> (String, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Requests::default();
+        let log = requests.clone();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+
+                let length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .map_or(0, |length| length.parse().unwrap());
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let body = String::from_utf8(body).unwrap();
+
+                let response = respond(&head, &body);
+                log.lock().unwrap().push((head, body));
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (base, requests)
+    }
+
+    fn response(status: &str, headers: &[&str], body: &str) -> String {
+        let headers = headers
+            .iter()
+            .map(|header| format!("{header}\r\n"))
+            .collect::<String>();
+
+        format!(
+            "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    fn card(name: &str, url: &str, extended: bool) -> AgentCard<'static> {
+        AgentCard::builder()
+            .name(name.to_owned())
+            .description("Plans routes")
+            .url(url.to_owned())
+            .version("1.0.0")
+            .default_input_modes([MediaType::TEXT_PLAIN])
+            .default_output_modes([MediaType::TEXT_PLAIN])
+            .skill(
+                AgentSkill::builder()
+                    .id("route")
+                    .name("Route")
+                    .description("Routes")
+                    .build()
+                    .unwrap(),
+            )
+            .supports_authenticated_extended_card(extended)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn legacy_path() {
+        let json = serde_json::to_string(&card("Planner", "http://localhost/a2a", false)).unwrap();
+        let (base, requests) = serve(move |head, _| {
+            if head.starts_with("GET /.well-known/agent.json ") {
+                // A chunked body, split inside the JSON.
+                let (first, second) = json.split_at(10);
+                format!(
+                    "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{first}\r\n{:x}\r\n{second}\r\n0\r\n\r\n",
+                    first.len(),
+                    second.len()
+                )
+            } else {
+                response("404 Not Found", &[], "")
+            }
+        });
+
+        let discovery = CardDiscovery::new(PlainHttpClient::new());
+        let discovered = discovery
+            .discover(&format!("{base}/agents/planner"))
+            .unwrap();
+
+        assert_eq!(discovered.url, format!("{base}{LEGACY_AGENT_CARD_PATH}"));
+        assert_eq!(discovered.card.name(), "Planner");
+        assert!(!discovered.cached);
+        assert!(discovered.extended_card.is_none());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0]
+            .0
+            .starts_with("GET /.well-known/agent-card.json HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn caching() {
+        let json = serde_json::to_string(&card("Planner", "http://localhost/a2a", false)).unwrap();
+        let fresh = json.clone();
+        let (base, requests) =
+            serve(move |_, _| response("200 OK", &["Cache-Control: public, max-age=60"], &fresh));
+
+        let discovery = CardDiscovery::new(PlainHttpClient::new());
+        assert!(!discovery.discover(&base).unwrap().cached);
+        assert!(discovery.discover(&base).unwrap().cached);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        discovery.clear_cache();
+        assert!(!discovery.discover(&base).unwrap().cached);
+
+        let (base, requests) = serve(move |head, _| {
+            if head.contains("If-None-Match: \"v1\"\r\n") {
+                // Without repeating the ETag.
+                response("304 Not Modified", &[], "")
+            } else {
+                response(
+                    "200 OK",
+                    &["ETag: \"v1\"", "Cache-Control: no-cache"],
+                    &json,
+                )
+            }
+        });
+
+        let first = discovery.discover(&base).unwrap();
+        let second = discovery.discover(&base).unwrap();
+        assert!(second.cached);
+        assert_eq!(first.card, second.card);
+        let third = discovery.discover(&base).unwrap();
+        assert!(third.cached);
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn errors() {
+        let (base, _) =
+            serve(
+                |head, _| match head.starts_with("GET /.well-known/agent-card.json") {
+                    true => response("500 Internal Server Error", &[], ""),
+                    false => response("404 Not Found", &[], ""),
+                },
+            );
+        let discovery = CardDiscovery::new(PlainHttpClient::new());
+
+        assert!(matches!(
+            discovery.discover(&base),
+            Err(DiscoveryError::Status { status: 500, .. })
+        ));
+        assert!(matches!(
+            discovery.discover("https://"),
+            Err(DiscoveryError::InvalidUrl(_))
+        ));
+        // There is no TLS client in the crate.
+        assert!(matches!(
+            discovery.discover("example.com"),
+            Err(DiscoveryError::Http(_))
+        ));
+
+        let (base, _) = serve(|_, _| response("404 Not Found", &[], ""));
+        assert!(matches!(
+            discovery.discover(&base),
+            Err(DiscoveryError::NotFound(origin)) if origin == base
+        ));
+    }
+
+    #[test]
+    fn extended_card() {
+        let listener_base = Arc::new(Mutex::new(String::new()));
+        let url = listener_base.clone();
+
+        let (base, requests) = serve(move |head, body| {
+            let url = url.lock().unwrap().clone();
+
+            if head.starts_with("POST /a2a ") && body.contains("agent/getAuthenticatedExtendedCard")
+            {
+                let extended = match head.contains("Authorization: Bearer token\r\n") {
+                    true => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "result": card("Planner Pro", &url, true),
+                    }),
+                    false => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "error": {"code": -32007, "message": "Not configured"},
+                    }),
+                };
+                response("200 OK", &[], &extended.to_string())
+            } else {
+                let card = serde_json::to_string(&card("Planner", &url, true)).unwrap();
+                response("200 OK", &[], &card)
+            }
+        });
+        *listener_base.lock().unwrap() = format!("{base}/a2a");
+
+        let discovery = CardDiscovery::new(PlainHttpClient::new());
+        assert!(discovery.discover(&base).unwrap().extended_card.is_none());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let discovery = CardDiscovery::new(PlainHttpClient::new())
+            .with_authorization("Authorization", "Bearer token");
+        let discovered = discovery.discover(&base).unwrap();
+        assert_eq!(discovered.card.name(), "Planner");
+        assert_eq!(
+            discovered.extended_card.unwrap().unwrap().name(),
+            "Planner Pro"
+        );
+
+        // A failed extended card still gives the public card.
+        let discovery = CardDiscovery::new(PlainHttpClient::new())
+            .with_authorization("Authorization", "Bearer other");
+        let discovered = discovery.discover(&base).unwrap();
+        assert_eq!(discovered.card.name(), "Planner");
+        assert!(matches!(
+            discovered.extended_card,
+            Some(Err(DiscoveryError::JsonRpc(error)))
+                if error.code == JsonRpcErrorCode::AuthenticatedExtendedCardNotConfiguredError
+        ));
+
+        // The authorization is not sent to the interface of a card found at another origin.
+        let foreign =
+            serde_json::to_string(&card("Planner", &format!("{base}/a2a"), true)).unwrap();
+        let (foreign_base, _) = serve(move |_, _| response("200 OK", &[], &foreign));
+        let sent = requests.lock().unwrap().len();
+        let discovered = discovery.discover(&foreign_base).unwrap();
+        assert!(matches!(
+            discovered.extended_card,
+            Some(Err(DiscoveryError::UntrustedExtendedCardUrl(url))) if url == format!("{base}/a2a")
+        ));
+        assert_eq!(requests.lock().unwrap().len(), sent);
+    }
+
+    #[cfg(feature = "jws")]
+    #[test]
+    fn signed_cards() {
+        use ring::{rand::SystemRandom, signature::Ed25519KeyPair};
+
+        use crate::{CardSignatureError, JwkSet, JwsAlgorithm, SigningKey};
+
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key = SigningKey::from_pkcs8(JwsAlgorithm::EdDsa, pkcs8.as_ref())
+            .unwrap()
+            .with_key_id("key-1");
+
+        let mut signed = card("Planner", "http://localhost/a2a", false);
+        signed.sign(&key).unwrap();
+        let json = serde_json::to_string(&signed).unwrap();
+        // The same card with a member added after it was signed.
+        let extended = json.replacen('{', r#"{"x-partner":"gold","#, 1);
+        let (base, _) = serve(move |_, _| response("200 OK", &[], &json));
+        let (extended_base, _) = serve(move |_, _| response("200 OK", &[], &extended));
+
+        let discovery = CardDiscovery::new(PlainHttpClient::new())
+            .with_key_resolver(JwkSet::new().with_key(key.public_jwk()));
+        assert_eq!(discovery.discover(&base).unwrap().card, signed);
+        assert!(matches!(
+            discovery.discover(&extended_base),
+            Err(DiscoveryError::Signature(CardSignatureError::Mismatch))
+        ));
+
+        let discovery = CardDiscovery::new(PlainHttpClient::new()).with_key_resolver(JwkSet::new());
+        assert!(matches!(
+            discovery.discover(&base),
+            Err(DiscoveryError::Signature(CardSignatureError::KeyNotFound(
+                _
+            )))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod interface_selection_sanity_checks {
+    use std::borrow::Cow;
+
+    use crate::{
+        connect_with_fallback, AgentCard, AgentInterface, AgentSkill, Fallback, MediaType,
+        TransportProtocol,
+    };
+
+    fn interface(url: &str, transport: TransportProtocol) -> AgentInterface<'_> {
+        AgentInterface {
+            url: Cow::Borrowed(url),
+            transport,
+        }
+    }
+
+    fn card(preferred: Option<TransportProtocol>) -> AgentCard<'static> {
+        let builder = AgentCard::builder()
+            .name("Planner")
+            .description("Plans routes")
+            .url("https://grpc.example.com/a2a")
+            .version("1.0.0")
+            .default_input_modes([MediaType::TEXT_PLAIN])
+            .default_output_modes([MediaType::TEXT_PLAIN])
+            .skill(
+                AgentSkill::builder()
+                    .id("route")
+                    .name("Route")
+                    .description("Routes")
+                    .build()
+                    .unwrap(),
+            )
+            .additional_interface(interface(
+                "https://api.example.com/a2a/v1",
+                TransportProtocol::JsonRpc,
+            ))
+            .additional_interface(interface(
+                "https://grpc.example.com/a2a",
+                TransportProtocol::Grpc,
+            ))
+            .additional_interface(interface(
+                "https://rest.example.com/v1",
+                TransportProtocol::HttpJson,
+            ))
+            .additional_interface(interface(
+                "https://mqtt.example.com/a2a",
+                TransportProtocol::from("MQTT"),
+            ))
+            .additional_interface(interface(
+                "https://api.example.com/a2a/v1",
+                TransportProtocol::JsonRpc,
+            ));
+
+        match preferred {
+            Some(transport) => builder.preferred_transport(transport),
+            None => builder,
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn select() {
+        let grpc = card(Some(TransportProtocol::Grpc));
+
+        assert_eq!(
+            grpc.select_interfaces(&[
+                TransportProtocol::HttpJson,
+                TransportProtocol::JsonRpc,
+                TransportProtocol::Grpc
+            ]),
+            [
+                interface("https://grpc.example.com/a2a", TransportProtocol::Grpc),
+                interface("https://api.example.com/a2a/v1", TransportProtocol::JsonRpc),
+                interface("https://rest.example.com/v1", TransportProtocol::HttpJson),
+            ]
+        );
+        assert_eq!(
+            grpc.select_interfaces(&[TransportProtocol::JsonRpc]),
+            [interface(
+                "https://api.example.com/a2a/v1",
+                TransportProtocol::JsonRpc
+            )]
+        );
+        assert!(grpc.select_interfaces(&[]).is_empty());
+
+        // Unknown transports are kept as written and only selected when they are supported.
+        let mqtt = serde_json::from_str::<AgentInterface>(
+            r#"{"url": "https://mqtt.example.com/a2a", "transport": "MQTT"}"#,
+        )
+        .unwrap();
+        assert_eq!(mqtt.transport, TransportProtocol::Other("MQTT".into()));
+        assert_eq!(serde_json::to_value(&mqtt).unwrap()["transport"], "MQTT");
+        assert_eq!(
+            grpc.select_interfaces(&[TransportProtocol::Other("MQTT".into())]),
+            [mqtt]
+        );
+
+        // The main url is JSON-RPC when no transport is preferred.
+        assert_eq!(
+            card(None).select_interfaces(&[TransportProtocol::JsonRpc]),
+            [
+                interface("https://grpc.example.com/a2a", TransportProtocol::JsonRpc),
+                interface("https://api.example.com/a2a/v1", TransportProtocol::JsonRpc),
+            ]
+        );
+    }
+
+    #[test]
+    fn fallback() {
+        let card = card(Some(TransportProtocol::Grpc));
+        let supported = [TransportProtocol::JsonRpc, TransportProtocol::HttpJson];
+
+        let mut tried = Vec::new();
+        let connected = card.connect(&supported, |interface| {
+            tried.push(interface.url.to_string());
+            match interface.transport {
+                TransportProtocol::HttpJson => Ok(interface.url.to_string()),
+                _ => Err(Fallback::Next("connection refused")),
+            }
+        });
+        assert_eq!(connected.unwrap(), "https://rest.example.com/v1");
+        assert_eq!(
+            tried,
+            [
+                "https://api.example.com/a2a/v1",
+                "https://rest.example.com/v1"
+            ]
+        );
+
+        let error = card
+            .connect(&supported, |_| {
+                Err::<(), _>(Fallback::Next("connection refused"))
+            })
+            .unwrap_err();
+        assert!(!error.is_unsupported());
+        assert!(!error.stopped);
+        assert_eq!(error.failures.len(), 2);
+        assert_eq!(error.last_error(), Some(&"connection refused"));
+        assert_eq!(
+            error.to_string(),
+            "Connecting to the agent failed on 2 interface(s), the last at \
+            `https://rest.example.com/v1` over HTTP+JSON: connection refused"
+        );
+
+        // A rejected authorization is not retried on the other interfaces.
+        let mut tried = 0;
+        let error = card
+            .connect(&supported, |_| {
+                tried += 1;
+                Err::<(), _>(Fallback::Stop("unauthorized"))
+            })
+            .unwrap_err();
+        assert_eq!(tried, 1);
+        assert!(error.stopped);
+        assert_eq!(error.failures.len(), 1);
+        assert_eq!(
+            error.to_string(),
+            "Connecting to the agent failed on 1 interface(s) before it was stopped, the last at \
+            `https://api.example.com/a2a/v1` over JSONRPC: unauthorized"
+        );
+
+        let error = connect_with_fallback([], |_| Ok::<_, Fallback<&str>>(())).unwrap_err();
+        assert!(error.is_unsupported());
+        assert_eq!(
+            error.to_string(),
+            "The agent has no interface with a supported transport"
+        );
+    }
+}
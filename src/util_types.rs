@@ -1,8 +1,874 @@
-use std::collections::BTreeMap;
+use std::{
+    borrow::Cow, cmp::Ordering, collections::BTreeMap, fmt, hash::Hash, marker::PhantomData,
+};
 
-/// The `JsonStr` is a JSON &str that is not standardized
-/// It is be domain specific
-pub type JsonStr<'a> = &'a str;
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, SeqDeserializer},
+        DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess,
+        Visitor,
+    },
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::value::RawValue;
 
-pub type OpenIdExtension<'a> = BTreeMap<String, JsonStr<'a>>;
-pub type JsonStrMemKV<'a> = BTreeMap<String, JsonStr<'a>>;
+use crate::IntoOwned;
+
+/// The members of a JSON object.
+pub type JsonObject<'a> = BTreeMap<Cow<'a, str>, JsonValue<'a>>;
+
+/// Vendor extension members (`x-*`) of a security scheme. Their values are domain specific.
+pub type OpenIdExtension<'a> = JsonObject<'a>;
+/// Free-form key-value metadata, e.g. the `metadata` member of messages, tasks and artifacts.
+pub type JsonStrMemKV<'a> = JsonObject<'a>;
+
+/// Any JSON value. Strings and object keys borrow from the input when they contain no escape sequences.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub enum JsonValue<'a> {
+    #[default]
+    Null,
+    Bool(bool),
+    Number(JsonNumber),
+    String(Cow<'a, str>),
+    Array(Vec<JsonValue<'a>>),
+    Object(JsonObject<'a>),
+    /// JSON text passed through as is, never parsed unless it is inspected or decoded.
+    /// It is only ever built by hand, deserializing a [JsonValue] never produces it.
+    Raw(RawJson<'a>),
+}
+
+impl<'a> JsonValue<'a> {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&JsonNumber> {
+        match self {
+            Self::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue<'a>]> {
+        match self {
+            Self::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&JsonObject<'a>> {
+        match self {
+            Self::Object(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Look up a member of an object. Returns `None` for any other kind of value.
+    pub fn get(&self, key: &str) -> Option<&JsonValue<'a>> {
+        self.as_object()?.get(key)
+    }
+
+    /// Decode the value into `T`, borrowing the strings `T` borrows from the value.
+    pub fn deserialize_into<T>(self) -> Result<T, de::value::Error>
+    where
+        T: Deserialize<'a>,
+    {
+        T::deserialize(self.into_deserializer())
+    }
+
+    /// Copy every borrowed string so the value no longer borrows from its input.
+    pub fn into_owned(self) -> JsonValue<'static> {
+        match self {
+            Self::Null => JsonValue::Null,
+            Self::Bool(value) => JsonValue::Bool(value),
+            Self::Number(value) => JsonValue::Number(value),
+            Self::String(value) => JsonValue::String(Cow::Owned(value.into_owned())),
+            Self::Array(values) => {
+                JsonValue::Array(values.into_iter().map(Self::into_owned).collect())
+            }
+            Self::Object(members) => JsonValue::Object(
+                members
+                    .into_iter()
+                    .map(|(key, value)| (Cow::Owned(key.into_owned()), value.into_owned()))
+                    .collect(),
+            ),
+            Self::Raw(raw) => JsonValue::Raw(raw.into_owned()),
+        }
+    }
+
+    /// Parse a [JsonValue::Raw] into the value it holds. Any other value is returned unchanged.
+    pub fn expand(self) -> Result<Self, serde_json::Error> {
+        match self {
+            Self::Raw(raw) => raw.parse(),
+            value => Ok(value),
+        }
+    }
+}
+
+impl<'a> From<bool> for JsonValue<'a> {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl<'a> From<&'a str> for JsonValue<'a> {
+    fn from(value: &'a str) -> Self {
+        Self::String(Cow::Borrowed(value))
+    }
+}
+
+impl<'a> From<String> for JsonValue<'a> {
+    fn from(value: String) -> Self {
+        Self::String(Cow::Owned(value))
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for JsonValue<'a> {
+    fn from(value: Cow<'a, str>) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<'a> From<JsonNumber> for JsonValue<'a> {
+    fn from(value: JsonNumber) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl<'a> From<i64> for JsonValue<'a> {
+    fn from(value: i64) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl<'a> From<u64> for JsonValue<'a> {
+    fn from(value: u64) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl<'a> From<f64> for JsonValue<'a> {
+    fn from(value: f64) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl<'a> From<Vec<JsonValue<'a>>> for JsonValue<'a> {
+    fn from(value: Vec<JsonValue<'a>>) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl<'a> From<JsonObject<'a>> for JsonValue<'a> {
+    fn from(value: JsonObject<'a>) -> Self {
+        Self::Object(value)
+    }
+}
+
+impl<'a> From<RawJson<'a>> for JsonValue<'a> {
+    fn from(value: RawJson<'a>) -> Self {
+        Self::Raw(value)
+    }
+}
+
+impl<'a> FromIterator<(Cow<'a, str>, JsonValue<'a>)> for JsonValue<'a> {
+    fn from_iter<T: IntoIterator<Item = (Cow<'a, str>, JsonValue<'a>)>>(iter: T) -> Self {
+        Self::Object(iter.into_iter().collect())
+    }
+}
+
+impl<'a> Serialize for JsonValue<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Null => serializer.serialize_unit(),
+            Self::Bool(value) => serializer.serialize_bool(*value),
+            Self::Number(value) => value.serialize(serializer),
+            Self::String(value) => serializer.serialize_str(value),
+            Self::Array(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            Self::Object(members) => {
+                let mut map = serializer.serialize_map(Some(members.len()))?;
+                for (key, value) in members {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            Self::Raw(raw) => raw.serialize(serializer),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for JsonValue<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(JsonValueVisitor)
+    }
+}
+
+struct JsonValueVisitor;
+
+impl<'de> Visitor<'de> for JsonValueVisitor {
+    type Value = JsonValue<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(JsonValue::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(JsonValue::Number(value.into()))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(JsonValue::Number(value.into()))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(JsonValue::Number(value.into()))
+    }
+
+    fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E> {
+        Ok(JsonValue::String(Cow::Borrowed(value)))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(JsonValue::String(Cow::Owned(value.to_owned())))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+        Ok(JsonValue::String(Cow::Owned(value)))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        JsonValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+
+        Ok(JsonValue::Array(values))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut members = BTreeMap::new();
+        while let Some(JsonKey(key)) = map.next_key()? {
+            members.insert(key, map.next_value()?);
+        }
+
+        Ok(JsonValue::Object(members))
+    }
+}
+
+/// An object key, or any other string, that borrows from the input when possible.
+pub(crate) struct JsonKey<'a>(pub(crate) Cow<'a, str>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for JsonKey<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct JsonKeyVisitor;
+
+        impl<'de> Visitor<'de> for JsonKeyVisitor {
+            type Value = JsonKey<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E> {
+                Ok(JsonKey(Cow::Borrowed(value)))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(JsonKey(Cow::Owned(value.to_owned())))
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+                Ok(JsonKey(Cow::Owned(value)))
+            }
+        }
+
+        deserializer.deserialize_str(JsonKeyVisitor)
+    }
+}
+
+/// JSON text that is kept exactly as received and written out unchanged,
+/// for payloads that are forwarded without being looked at.
+///
+/// Writing it out verbatim and borrowing it from the input both rely on `serde_json`,
+/// so it is deserialized with `serde_json::from_str` or `serde_json::from_slice`.
+/// Raw values are compared and hashed by their text.
+#[derive(Debug, Clone)]
+pub struct RawJson<'a>(Cow<'a, RawValue>);
+
+impl<'a> RawJson<'a> {
+    /// Check that `json` is valid JSON and keep it as is.
+    pub fn from_string(json: String) -> Result<RawJson<'static>, serde_json::Error> {
+        RawValue::from_string(json).map(|raw| RawJson(Cow::Owned(raw)))
+    }
+
+    /// The JSON text.
+    pub fn get(&self) -> &str {
+        self.0.get()
+    }
+
+    /// Parse the JSON text, borrowing strings from it where possible.
+    pub fn parse(self) -> Result<JsonValue<'a>, serde_json::Error> {
+        match self.0 {
+            Cow::Borrowed(raw) => serde_json::from_str(raw.get()),
+            Cow::Owned(raw) => {
+                serde_json::from_str::<JsonValue>(raw.get()).map(JsonValue::into_owned)
+            }
+        }
+    }
+
+    pub fn into_owned(self) -> RawJson<'static> {
+        RawJson(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+impl<'a> From<&'a RawValue> for RawJson<'a> {
+    fn from(value: &'a RawValue) -> Self {
+        Self(Cow::Borrowed(value))
+    }
+}
+
+impl From<Box<RawValue>> for RawJson<'static> {
+    fn from(value: Box<RawValue>) -> Self {
+        Self(Cow::Owned(value))
+    }
+}
+
+impl<'a> PartialEq for RawJson<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<'a> Eq for RawJson<'a> {}
+
+impl<'a> PartialOrd for RawJson<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for RawJson<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.get().cmp(other.get())
+    }
+}
+
+impl<'a> Hash for RawJson<'a> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.get().hash(state)
+    }
+}
+
+impl<'a> fmt::Display for RawJson<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.get())
+    }
+}
+
+impl<'a> Serialize for RawJson<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for RawJson<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <&'de RawValue>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// A JSON number, kept as an integer when it has no fractional part.
+/// Floats are compared and hashed by their bits so that [JsonValue] can be used as a map key.
+#[derive(Debug, Clone, Copy)]
+pub struct JsonNumber(Number);
+
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    PosInt(u64),
+    NegInt(i64),
+    Float(f64),
+}
+
+impl JsonNumber {
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.0 {
+            Number::PosInt(value) => i64::try_from(value).ok(),
+            Number::NegInt(value) => Some(value),
+            Number::Float(_) => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.0 {
+            Number::PosInt(value) => Some(value),
+            Number::NegInt(_) | Number::Float(_) => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self.0 {
+            Number::PosInt(value) => value as f64,
+            Number::NegInt(value) => value as f64,
+            Number::Float(value) => value,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        !matches!(self.0, Number::Float(_))
+    }
+}
+
+impl From<u64> for JsonNumber {
+    fn from(value: u64) -> Self {
+        Self(Number::PosInt(value))
+    }
+}
+
+impl From<i64> for JsonNumber {
+    fn from(value: i64) -> Self {
+        match u64::try_from(value) {
+            Ok(value) => Self(Number::PosInt(value)),
+            Err(_) => Self(Number::NegInt(value)),
+        }
+    }
+}
+
+impl From<i32> for JsonNumber {
+    fn from(value: i32) -> Self {
+        i64::from(value).into()
+    }
+}
+
+impl From<u32> for JsonNumber {
+    fn from(value: u32) -> Self {
+        u64::from(value).into()
+    }
+}
+
+impl From<f64> for JsonNumber {
+    fn from(value: f64) -> Self {
+        Self(Number::Float(value))
+    }
+}
+
+impl PartialEq for JsonNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for JsonNumber {}
+
+impl PartialOrd for JsonNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.0, other.0) {
+            (Number::PosInt(lhs), Number::PosInt(rhs)) => lhs.cmp(&rhs),
+            (Number::NegInt(lhs), Number::NegInt(rhs)) => lhs.cmp(&rhs),
+            (Number::Float(lhs), Number::Float(rhs)) => lhs.total_cmp(&rhs),
+            (Number::NegInt(_), _) => Ordering::Less,
+            (_, Number::NegInt(_)) => Ordering::Greater,
+            (Number::PosInt(_), Number::Float(_)) => Ordering::Less,
+            (Number::Float(_), Number::PosInt(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl Hash for JsonNumber {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self.0 {
+            Number::PosInt(value) => (0u8, value).hash(state),
+            Number::NegInt(value) => (1u8, value).hash(state),
+            Number::Float(value) => (2u8, value.to_bits()).hash(state),
+        }
+    }
+}
+
+impl fmt::Display for JsonNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Number::PosInt(value) => value.fmt(f),
+            Number::NegInt(value) => value.fmt(f),
+            Number::Float(value) => value.fmt(f),
+        }
+    }
+}
+
+impl Serialize for JsonNumber {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            Number::PosInt(value) => serializer.serialize_u64(value),
+            Number::NegInt(value) => serializer.serialize_i64(value),
+            Number::Float(value) => serializer.serialize_f64(value),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonNumber {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match JsonValue::deserialize(deserializer)? {
+            JsonValue::Number(number) => Ok(number),
+            _ => Err(de::Error::custom("expected a JSON number")),
+        }
+    }
+}
+
+impl<'de, E: de::Error> IntoDeserializer<'de, E> for JsonValue<'de> {
+    type Deserializer = JsonValueDeserializer<'de, E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        JsonValueDeserializer {
+            value: self,
+            error: PhantomData,
+        }
+    }
+}
+
+/// Deserializes any type out of a [JsonValue], passing borrowed strings on as borrowed.
+/// This lets a value be inspected (e.g. for its `kind`) before it is decoded into its final type.
+pub struct JsonValueDeserializer<'de, E> {
+    value: JsonValue<'de>,
+    error: PhantomData<E>,
+}
+
+impl<'de, E: de::Error> Deserializer<'de> for JsonValueDeserializer<'de, E> {
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value.expand().map_err(de::Error::custom)? {
+            JsonValue::Raw(_) => unreachable!("a raw value is always expanded"),
+            JsonValue::Null => visitor.visit_unit(),
+            JsonValue::Bool(value) => visitor.visit_bool(value),
+            JsonValue::Number(JsonNumber(Number::PosInt(value))) => visitor.visit_u64(value),
+            JsonValue::Number(JsonNumber(Number::NegInt(value))) => visitor.visit_i64(value),
+            JsonValue::Number(JsonNumber(Number::Float(value))) => visitor.visit_f64(value),
+            JsonValue::String(value) => {
+                CowStrDeserializer::<E>::new(value).deserialize_any(visitor)
+            }
+            JsonValue::Array(values) => {
+                let mut seq = SeqDeserializer::new(values.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+
+                Ok(value)
+            }
+            JsonValue::Object(members) => {
+                let mut map = MapDeserializer::new(
+                    members
+                        .into_iter()
+                        .map(|(key, value)| (CowStrDeserializer::<E>::new(key), value)),
+                );
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value.expand().map_err(de::Error::custom)? {
+            JsonValue::Null => visitor.visit_none(),
+            value => visitor.visit_some(value.into_deserializer()),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value.expand().map_err(de::Error::custom)? {
+            JsonValue::String(variant) => visitor.visit_enum(CowStrDeserializer::<E>::new(variant)),
+            JsonValue::Object(members) if members.len() == 1 => {
+                let (variant, value) = members.into_iter().next().unwrap_or_default();
+                visitor.visit_enum(EnumDeserializer {
+                    variant: CowStrDeserializer::new(variant),
+                    value: value.into_deserializer(),
+                })
+            }
+            _ => Err(de::Error::custom(
+                "expected a string or an object with a single member for an enum",
+            )),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// A string that is visited as borrowed when it borrows from the input.
+struct CowStrDeserializer<'de, E> {
+    value: Cow<'de, str>,
+    error: PhantomData<E>,
+}
+
+impl<'de, E> CowStrDeserializer<'de, E> {
+    fn new(value: Cow<'de, str>) -> Self {
+        Self {
+            value,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<'de, E: de::Error> IntoDeserializer<'de, E> for CowStrDeserializer<'de, E> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de, E: de::Error> Deserializer<'de> for CowStrDeserializer<'de, E> {
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Cow::Borrowed(value) => visitor.visit_borrowed_str(value),
+            Cow::Owned(value) => visitor.visit_string(value),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de, E: de::Error> EnumAccess<'de> for CowStrDeserializer<'de, E> {
+    type Error = E;
+    type Variant = UnitOnly<E>;
+
+    fn variant_seed<T>(self, seed: T) -> Result<(T::Value, Self::Variant), Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Ok((seed.deserialize(self)?, UnitOnly(PhantomData)))
+    }
+}
+
+/// The variant of an enum given as a plain string, which can only be a unit variant.
+struct UnitOnly<E>(PhantomData<E>);
+
+impl<'de, E: de::Error> VariantAccess<'de> for UnitOnly<E> {
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"newtype variant",
+        ))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"tuple variant",
+        ))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"struct variant",
+        ))
+    }
+}
+
+/// The variant of an enum given as an object with a single member.
+struct EnumDeserializer<'de, E> {
+    variant: CowStrDeserializer<'de, E>,
+    value: JsonValueDeserializer<'de, E>,
+}
+
+impl<'de, E: de::Error> EnumAccess<'de> for EnumDeserializer<'de, E> {
+    type Error = E;
+    type Variant = JsonValueDeserializer<'de, E>;
+
+    fn variant_seed<T>(self, seed: T) -> Result<(T::Value, Self::Variant), Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Ok((seed.deserialize(self.variant)?, self.value))
+    }
+}
+
+impl<'de, E: de::Error> VariantAccess<'de> for JsonValueDeserializer<'de, E> {
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::IgnoredAny::deserialize(self).map(drop)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+}
+
+impl<'a> IntoOwned for JsonValue<'a> {
+    type Owned = JsonValue<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        JsonValue::into_owned(self)
+    }
+}
+
+impl<'a> IntoOwned for RawJson<'a> {
+    type Owned = RawJson<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        RawJson::into_owned(self)
+    }
+}